@@ -0,0 +1,42 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use day01::two_sum;
+
+// Even pseudo-random values, from a linear congruential generator, so that
+// no pair sums to an odd target and both searches scan the whole input.
+fn large_input(len: usize) -> Vec<i32> {
+    let mut state: u32 = 2020;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (state >> 8) as i32 & !1
+        })
+        .collect()
+}
+
+// Tries every pair, as part 1 was solved before `two_sum`.
+fn two_sum_nested(values: &[i32], target: i32) -> Option<(i32, i32)> {
+    for (i, &a) in values.iter().enumerate() {
+        for &b in &values[i + 1..] {
+            if i64::from(a) + i64::from(b) == i64::from(target) {
+                return Some((a, b));
+            }
+        }
+    }
+    None
+}
+
+fn bench_two_sum(c: &mut Criterion) {
+    let values = large_input(10_000);
+    assert_eq!(two_sum(&values, 1), None);
+    assert_eq!(two_sum_nested(&values, 1), None);
+
+    let mut group = c.benchmark_group("two_sum");
+    group.sample_size(10);
+    group.bench_function("hash_set", |b| b.iter(|| two_sum(&values, 1)));
+    group.bench_function("nested_loops", |b| b.iter(|| two_sum_nested(&values, 1)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_two_sum);
+criterion_main!(benches);
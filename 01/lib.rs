@@ -0,0 +1,379 @@
+use aoc::Solver;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::io::{self, BufRead};
+use std::num::ParseIntError;
+
+pub const TARGET_SUM: i32 = 2020;
+
+pub fn solve_part1(values: &[i32]) -> Option<i64> {
+    pair_product(values, TARGET_SUM)
+}
+
+pub fn solve_part2(values: &[i32]) -> Option<i64> {
+    triple_product(values, TARGET_SUM)
+}
+
+// Finds two values at distinct positions summing to `target` in a single
+// pass, remembering the values seen so far.
+pub fn two_sum(values: &[i32], target: i32) -> Option<(i32, i32)> {
+    two_sum_wide(values, i64::from(target))
+}
+
+// Same as `two_sum`, but for a target out of the i32 range. The complement
+// of each value is skipped when it is out of the i32 range too.
+fn two_sum_wide(values: &[i32], target: i64) -> Option<(i32, i32)> {
+    let mut seen = HashSet::new();
+
+    for &n in values {
+        if let Ok(diff) = i32::try_from(target - i64::from(n)) {
+            if seen.contains(&diff) {
+                return Some((diff, n));
+            }
+        }
+        seen.insert(n);
+    }
+
+    None
+}
+
+// Same as `two_sum`, but reads the values one line at a time, stopping as
+// soon as the pair is found. Lines that are not numbers are reported as I/O
+// errors of kind `InvalidData`.
+pub fn two_sum_streaming(reader: impl BufRead, target: i32) -> io::Result<Option<(i32, i32)>> {
+    let mut seen = HashSet::new();
+
+    for line in reader.lines() {
+        let n: i32 = line?
+            .trim_end_matches('\r')
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if let Ok(diff) = i32::try_from(i64::from(target) - i64::from(n)) {
+            if seen.contains(&diff) {
+                return Ok(Some((diff, n)));
+            }
+        }
+        seen.insert(n);
+    }
+
+    Ok(None)
+}
+
+// Products are computed as i64, so they cannot overflow for two values.
+pub fn pair_product(values: &[i32], target: i32) -> Option<i64> {
+    two_sum(values, target).map(|(a, b)| i64::from(a) * i64::from(b))
+}
+
+// Only values at distinct positions are combined, so repeated values are
+// handled correctly. Returns `None` as well if the product overflows an i64.
+pub fn triple_product(values: &[i32], target: i32) -> Option<i64> {
+    let (a, b, c) = values.iter().enumerate().find_map(|(i, &a)| {
+        two_sum_wide(&values[i + 1..], i64::from(target) - i64::from(a)).map(|(b, c)| (a, b, c))
+    })?;
+
+    i64::from(a)
+        .checked_mul(i64::from(b))?
+        .checked_mul(i64::from(c))
+}
+
+// Sorts the values once, then for each value scans the ones after it from
+// both ends for the remaining two, in O(n^2) time.
+pub fn three_sum_sorted(values: &[i32], target: i32) -> Option<i64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+
+    for (i, &a) in sorted.iter().enumerate() {
+        let (mut lo, mut hi) = (i + 1, sorted.len());
+        while lo + 1 < hi {
+            let (b, c) = (sorted[lo], sorted[hi - 1]);
+            match (i64::from(a) + i64::from(b) + i64::from(c)).cmp(&i64::from(target)) {
+                Ordering::Equal => {
+                    return i64::from(a)
+                        .checked_mul(i64::from(b))?
+                        .checked_mul(i64::from(c))
+                }
+                Ordering::Less => lo += 1,
+                Ordering::Greater => hi -= 1,
+            }
+        }
+    }
+
+    None
+}
+
+// Returns every distinct combination of three values, at distinct positions,
+// summing to `target`. Each triple is sorted and the triples are returned in
+// ascending order.
+pub fn all_triples(values: &[i32], target: i32) -> Vec<(i32, i32, i32)> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mut triples = vec![];
+
+    for (i, &a) in sorted.iter().enumerate() {
+        if i > 0 && sorted[i - 1] == a {
+            continue;
+        }
+
+        let (mut lo, mut hi) = (i + 1, sorted.len());
+        while lo + 1 < hi {
+            let (b, c) = (sorted[lo], sorted[hi - 1]);
+            match (i64::from(a) + i64::from(b) + i64::from(c)).cmp(&i64::from(target)) {
+                Ordering::Equal => {
+                    triples.push((a, b, c));
+                    while lo + 1 < hi && sorted[lo] == b {
+                        lo += 1;
+                    }
+                }
+                Ordering::Less => lo += 1,
+                Ordering::Greater => hi -= 1,
+            }
+        }
+    }
+
+    triples
+}
+
+// Finds `k` values summing to `target`, searching the sorted values
+// recursively down to a two-pointer scan for the last two.
+pub fn find_subset_sum(values: &[i32], target: i32, k: usize) -> Option<Vec<i32>> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    find_subset_sum_sorted(&sorted, i64::from(target), k)
+}
+
+// The remaining target is kept as i64, as subtracting the values picked so
+// far can take it out of the i32 range.
+fn find_subset_sum_sorted(values: &[i32], target: i64, k: usize) -> Option<Vec<i32>> {
+    match k {
+        0 if target == 0 => Some(vec![]),
+        0 => None,
+        1 => {
+            let target = i32::try_from(target).ok()?;
+            values.binary_search(&target).ok().map(|_| vec![target])
+        }
+        2 => {
+            let (mut lo, mut hi) = (0, values.len());
+            while lo + 1 < hi {
+                let sum = i64::from(values[lo]) + i64::from(values[hi - 1]);
+                match sum.cmp(&target) {
+                    Ordering::Equal => return Some(vec![values[lo], values[hi - 1]]),
+                    Ordering::Less => lo += 1,
+                    Ordering::Greater => hi -= 1,
+                }
+            }
+            None
+        }
+        _ => {
+            for (i, &n) in values.iter().enumerate() {
+                if i > 0 && values[i - 1] == n {
+                    continue;
+                }
+                if let Some(mut rest) =
+                    find_subset_sum_sorted(&values[i + 1..], target - i64::from(n), k - 1)
+                {
+                    rest.insert(0, n);
+                    return Some(rest);
+                }
+            }
+            None
+        }
+    }
+}
+
+pub struct ExpenseReport;
+
+impl Solver for ExpenseReport {
+    type Input = Vec<i32>;
+    type Err = ParseIntError;
+
+    fn parse(s: &str) -> Result<Self::Input, Self::Err> {
+        s.lines().map(|line| line.parse()).collect()
+    }
+
+    fn part1(values: &Vec<i32>) -> String {
+        format!("{:?}", solve_part1(values))
+    }
+
+    fn part2(values: &Vec<i32>) -> String {
+        format!("{:?}", solve_part2(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+    const INPUT: &str = include_str!("input.txt");
+
+    #[test]
+    fn test_example_input() {
+        let values = ExpenseReport::parse(EXAMPLE).unwrap();
+        assert_eq!(solve_part1(&values), Some(514579));
+        assert_eq!(solve_part2(&values), Some(241861950));
+    }
+
+    #[test]
+    fn test_puzzle_input() {
+        let values = ExpenseReport::parse(INPUT).unwrap();
+        assert_eq!(solve_part1(&values), Some(918339));
+        assert_eq!(solve_part2(&values), Some(23869440));
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        let values = ExpenseReport::parse(&EXAMPLE.replace('\n', "\r\n")).unwrap();
+        assert_eq!(values, ExpenseReport::parse(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn test_repeated_values() {
+        assert_eq!(solve_part1(&[1010, 1010, 1000]), Some(1020100));
+        assert_eq!(solve_part1(&[1010, 1000]), None);
+        assert_eq!(solve_part2(&[1000, 20, 1000]), Some(20000000));
+    }
+
+    #[test]
+    fn test_two_sum() {
+        assert_eq!(
+            two_sum(&[1721, 979, 366, 299, 675, 1456], 2020),
+            Some((1721, 299))
+        );
+        assert_eq!(two_sum(&[5, 1, 5], 10), Some((5, 5)));
+        assert_eq!(two_sum(&[5, 1], 10), None);
+        assert_eq!(two_sum(&[1, -1], i32::MIN), None);
+        assert_eq!(two_sum(&[i32::MIN, -1, 0], i32::MIN), Some((i32::MIN, 0)));
+        assert_eq!(
+            two_sum(&[i32::MAX, 1, -1], i32::MAX - 1),
+            Some((i32::MAX, -1))
+        );
+    }
+
+    #[test]
+    fn test_two_sum_streaming() {
+        let found = two_sum_streaming(EXAMPLE.as_bytes(), TARGET_SUM).unwrap();
+        assert_eq!(found, Some((1721, 299)));
+        let found = two_sum_streaming(&b"5\r\n1\r\n5\r\n"[..], 10).unwrap();
+        assert_eq!(found, Some((5, 5)));
+        assert_eq!(two_sum_streaming(&b""[..], 10).unwrap(), None);
+
+        // Sums of multiples of 3 never reach the target, until the last line.
+        let mut content: String = (0..1_000_000).map(|n| format!("{}\n", n * 3)).collect();
+        content.push_str("1\n");
+        let found = two_sum_streaming(content.as_bytes(), 2_999_998).unwrap();
+        assert_eq!(found, Some((2_999_997, 1)));
+        assert_eq!(two_sum_streaming(content.as_bytes(), 2).unwrap(), None);
+
+        let found = two_sum_streaming(&b"1\n-1\n"[..], i32::MIN).unwrap();
+        assert_eq!(found, None);
+        let found = two_sum_streaming(&b"-1\n0\n-2147483648\n"[..], i32::MIN).unwrap();
+        assert_eq!(found, Some((0, i32::MIN)));
+
+        let err = two_sum_streaming(&b"1\ntwo\n"[..], 10).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_triple_product() {
+        assert_eq!(triple_product(&[1, 2, 3, 4], 9), Some(24));
+        assert_eq!(triple_product(&[3, 1, 3, 3], 9), Some(27));
+        assert_eq!(triple_product(&[3, 1, 3], 9), None);
+        assert_eq!(triple_product(&[], 9), None);
+        assert_eq!(triple_product(&[1, 2, 3], i32::MIN), None);
+        assert_eq!(triple_product(&[1, 2, 3], i32::MAX), None);
+        let values = [-2_147_000_000, 7, -483_000, -648];
+        let product = Some(-2_147_000_000 * -483_000 * -648);
+        assert_eq!(triple_product(&values, i32::MIN), product);
+    }
+
+    #[test]
+    fn test_three_sum_sorted() {
+        for content in &[EXAMPLE, INPUT] {
+            let values = ExpenseReport::parse(content).unwrap();
+            assert_eq!(three_sum_sorted(&values, TARGET_SUM), solve_part2(&values));
+        }
+
+        assert_eq!(three_sum_sorted(&[3, 1, 3, 3], 9), Some(27));
+        assert_eq!(three_sum_sorted(&[3, 1, 3], 9), None);
+        assert_eq!(three_sum_sorted(&[1000, 20, 1000], 2020), Some(20000000));
+        assert_eq!(three_sum_sorted(&[1000, 20], 2020), None);
+        assert_eq!(three_sum_sorted(&[], 9), None);
+    }
+
+    #[test]
+    fn test_all_triples() {
+        let values = ExpenseReport::parse(INPUT).unwrap();
+        let triples = all_triples(&values, TARGET_SUM);
+        assert_eq!(triples.len(), 1);
+        let (a, b, c) = triples[0];
+        assert_eq!(
+            Some(i64::from(a) * i64::from(b) * i64::from(c)),
+            solve_part2(&values)
+        );
+
+        assert_eq!(
+            all_triples(&[1, 2, 3, 4, 5, 6], 10),
+            vec![(1, 3, 6), (1, 4, 5), (2, 3, 5)]
+        );
+        assert_eq!(
+            all_triples(&[1000, 20, 1000, 1000], 2020),
+            vec![(20, 1000, 1000)]
+        );
+        assert_eq!(all_triples(&[1000, 20, 1020], 2020), vec![]);
+        assert_eq!(all_triples(&[1000, 20], 2020), vec![]);
+        assert_eq!(all_triples(&[2, 2, 2, 2], 6), vec![(2, 2, 2)]);
+    }
+
+    #[test]
+    fn test_large_products() {
+        let values = [1_000_000, 999_000, 1_000_001];
+        assert_eq!(pair_product(&values, 2_000_001), Some(1_000_001_000_000));
+        assert_eq!(
+            triple_product(&values, 2_999_001),
+            Some(999_000_999_000_000_000)
+        );
+        let values = [2_000_000_000, -2_000_000_000, 2_000_000_000];
+        assert_eq!(triple_product(&values, 2_000_000_000), None);
+    }
+
+    #[test]
+    fn test_solver() {
+        let values = ExpenseReport::parse("1721\n979\n366\n299\n675\n1456").unwrap();
+        assert_eq!(ExpenseReport::part1(&values), "Some(514579)");
+        assert_eq!(ExpenseReport::part2(&values), "Some(241861950)");
+        assert!(ExpenseReport::parse("1721\nx").is_err());
+    }
+
+    #[test]
+    fn test_find_subset_sum() {
+        let product = |values: Vec<i32>| values.iter().product::<i32>();
+
+        let values = ExpenseReport::parse(EXAMPLE).unwrap();
+        let found = find_subset_sum(&values, TARGET_SUM, 2).map(product);
+        assert_eq!(found, Some(514579));
+        let found = find_subset_sum(&values, TARGET_SUM, 3).map(product);
+        assert_eq!(found, Some(241861950));
+
+        let values = ExpenseReport::parse(INPUT).unwrap();
+        let found = find_subset_sum(&values, TARGET_SUM, 2).map(product);
+        assert_eq!(found, Some(918339));
+        let found = find_subset_sum(&values, TARGET_SUM, 3).map(product);
+        assert_eq!(found, Some(23869440));
+
+        let found = find_subset_sum(&[10, 1, 5, 2, 4, 3], 20, 4).unwrap();
+        assert_eq!(found.len(), 4);
+        assert_eq!(found.iter().sum::<i32>(), 20);
+        assert_eq!(find_subset_sum(&[10, 1, 5, 2, 4, 3], 30, 4), None);
+
+        let values = [2_000_000_000, 2_000_000_000, -2_000_000_000];
+        assert_eq!(find_subset_sum(&values[..2], 0, 2), None);
+        assert_eq!(find_subset_sum(&values, i32::MIN, 1), None);
+        let found = find_subset_sum(&values, 2_000_000_000, 3);
+        assert_eq!(
+            found,
+            Some(vec![-2_000_000_000, 2_000_000_000, 2_000_000_000])
+        );
+        assert_eq!(find_subset_sum(&values, i32::MAX, 3), None);
+    }
+}
@@ -0,0 +1,45 @@
+use aoc::testing::{run, run_with_stdin};
+
+#[test]
+fn test_stdin() {
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_01"),
+        &[],
+        include_bytes!("../example.txt"),
+    );
+    assert_eq!(
+        output,
+        "Result (Part 1):Some(514579)\nResult (Part 2):Some(241861950)\n"
+    );
+}
+
+#[test]
+fn test_file() {
+    let output = run(env!("CARGO_BIN_EXE_01"), &["example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1):Some(514579)\nResult (Part 2):Some(241861950)\n"
+    );
+}
+
+#[test]
+fn test_extreme_target() {
+    for target in &["-2147483648", "2147483647"] {
+        let output = run(env!("CARGO_BIN_EXE_01"), &["example.txt", target]);
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "Result (Part 1):None\nResult (Part 2):None\n"
+        );
+    }
+}
+
+#[test]
+fn test_usage() {
+    let output = run(env!("CARGO_BIN_EXE_01"), &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("USAGE: "));
+}
@@ -1,5 +1,4 @@
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -27,7 +26,7 @@ impl PasswordPolicy {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> Vec<(PasswordPolicy, String)> {
-    fs::read_to_string(file_name)
+    aoc::read_input(file_name)
         .unwrap()
         .lines()
         .map(|x| {
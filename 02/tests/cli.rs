@@ -0,0 +1,30 @@
+use aoc::testing::{run, run_with_stdin};
+
+#[test]
+fn test_stdin() {
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_02"),
+        &[],
+        include_bytes!("../example.txt"),
+    );
+    assert_eq!(output, "Result (Part 1) 2\nResult (Part 2) 1\n");
+}
+
+#[test]
+fn test_file() {
+    let output = run(env!("CARGO_BIN_EXE_02"), &["example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1) 2\nResult (Part 2) 1\n"
+    );
+}
+
+#[test]
+fn test_usage() {
+    let output = run(env!("CARGO_BIN_EXE_02"), &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("USAGE: "));
+}
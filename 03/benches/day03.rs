@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use day03::Map;
+
+const ROWS: usize = 10_000;
+const COLS: usize = 31;
+
+// A map with a tree on roughly one in five cells, spread so that every
+// slope hits some of them.
+fn large_map() -> String {
+    (0..ROWS)
+        .map(|row| {
+            (0..COLS)
+                .map(|col| {
+                    if (row * 7 + col * 13) % 5 == 0 {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// The trees stored as a bitset, one bit per cell with each row padded to a
+// whole number of words, as `Map` did before it kept a `Cell` per position.
+struct Bitset {
+    words_per_row: usize,
+    trees: Vec<u64>,
+}
+
+impl Bitset {
+    fn new(content: &str) -> Self {
+        let words_per_row = COLS.div_ceil(64);
+        let mut trees = vec![0; ROWS * words_per_row];
+        for (row, col) in tree_positions(content) {
+            trees[row * words_per_row + col / 64] |= 1 << (col % 64);
+        }
+        Bitset {
+            words_per_row,
+            trees,
+        }
+    }
+
+    fn navigate(&self, right: usize, down: usize) -> usize {
+        let mut count = 0;
+        let (mut row, mut col) = (0, 0);
+
+        while row < ROWS {
+            if self.trees[row * self.words_per_row + col / 64] & (1 << (col % 64)) != 0 {
+                count += 1;
+            }
+            row += down;
+            col = (col + right) % COLS;
+        }
+
+        count
+    }
+}
+
+fn tree_positions(content: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    content.lines().enumerate().flat_map(|(row, line)| {
+        line.chars()
+            .enumerate()
+            .filter(|&(_, ch)| ch == '#')
+            .map(move |(col, _)| (row, col))
+    })
+}
+
+// The trees stored as positions in a `HashSet`, as `Map` did before the
+// bitset.
+fn navigate_hash_set(trees: &HashSet<(usize, usize)>, right: usize, down: usize) -> usize {
+    let mut count = 0;
+    let (mut row, mut col) = (0, 0);
+
+    while row < ROWS {
+        if trees.contains(&(row, col)) {
+            count += 1;
+        }
+        row += down;
+        col = (col + right) % COLS;
+    }
+
+    count
+}
+
+fn bench_large_map(c: &mut Criterion) {
+    let content = large_map();
+    let map = content.parse::<Map>().unwrap();
+    let bitset = Bitset::new(&content);
+    let trees: HashSet<_> = tree_positions(&content).collect();
+    assert_eq!(map.navigate_toboggan(3, 1), navigate_hash_set(&trees, 3, 1));
+    assert_eq!(bitset.navigate(3, 1), navigate_hash_set(&trees, 3, 1));
+
+    let mut group = c.benchmark_group("large_map");
+    group.bench_function("grid", |b| b.iter(|| map.navigate_toboggan(3, 1)));
+    group.bench_function("bitset", |b| b.iter(|| bitset.navigate(3, 1)));
+    group.bench_function("hash_set", |b| b.iter(|| navigate_hash_set(&trees, 3, 1)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_large_map);
+criterion_main!(benches);
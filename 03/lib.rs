@@ -0,0 +1,1045 @@
+use aoc::{AocError, Solver};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::iter;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MapPosition {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl MapPosition {
+    pub fn new(row: usize, col: usize) -> Self {
+        MapPosition { row, col }
+    }
+
+    // Moves down the slope, wrapping around the `ncols` columns of the map,
+    // which repeats to the right. The column is wrapped without overflowing
+    // however far right the step goes, and the row saturates instead.
+    pub fn step(&self, right: usize, down: usize, ncols: usize) -> MapPosition {
+        let (col, right) = (self.col % ncols, right % ncols);
+        let col = if right >= ncols - col {
+            right - (ncols - col)
+        } else {
+            col + right
+        };
+        MapPosition::new(self.row.saturating_add(down), col)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NavStats {
+    pub steps: usize,
+    pub trees: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TraversalReport {
+    pub rows_descended: usize,
+    pub times_wrapped: usize,
+    pub trees_hit: usize,
+}
+
+// A position outside of a map with the given dimensions.
+#[derive(Debug, PartialEq)]
+pub struct OutOfBounds {
+    pub pos: MapPosition,
+    pub dimensions: (usize, usize),
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "position ({}, {}) is outside of the {}x{} map",
+            self.pos.row, self.pos.col, self.dimensions.0, self.dimensions.1
+        )
+    }
+}
+
+impl Error for OutOfBounds {}
+
+// Maps that cannot be concatenated, as they are not as wide when stacked
+// vertically or not as tall when placed side by side.
+#[derive(Debug, PartialEq)]
+pub struct DimensionMismatch {
+    pub left: (usize, usize),
+    pub right: (usize, usize),
+}
+
+impl fmt::Display for DimensionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot concatenate a {}x{} map with a {}x{} map",
+            self.left.0, self.left.1, self.right.0, self.right.1
+        )
+    }
+}
+
+impl Error for DimensionMismatch {}
+
+// The terrain of a cell. Other than the open squares and trees of the
+// puzzle, any character is kept as is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cell {
+    Open,
+    Tree,
+    Other(char),
+}
+
+impl From<char> for Cell {
+    fn from(ch: char) -> Self {
+        match ch {
+            '.' => Cell::Open,
+            '#' => Cell::Tree,
+            _ => Cell::Other(ch),
+        }
+    }
+}
+
+impl From<Cell> for char {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Open => '.',
+            Cell::Tree => '#',
+            Cell::Other(ch) => ch,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum MapParseError {
+    Io(io::Error),
+    RaggedRow(usize, usize, usize),
+    Empty,
+    // The 1-based line of a run-length encoded row that cannot be decoded.
+    InvalidRun(usize),
+}
+
+impl fmt::Display for MapParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapParseError::Io(err) => write!(f, "{}", err),
+            MapParseError::RaggedRow(line, width, expected) => write!(
+                f,
+                "line {}: row has {} columns, expected {}",
+                line, width, expected
+            ),
+            MapParseError::Empty => write!(f, "the map is empty"),
+            MapParseError::InvalidRun(line) => {
+                write!(f, "line {}: invalid run-length encoding", line)
+            }
+        }
+    }
+}
+
+impl Error for MapParseError {}
+
+impl From<MapParseError> for AocError {
+    fn from(err: MapParseError) -> Self {
+        match err {
+            MapParseError::Io(err) => AocError::Io(err),
+            MapParseError::RaggedRow(line, width, expected) => AocError::RaggedMap {
+                line,
+                width,
+                expected,
+            },
+            MapParseError::Empty => AocError::EmptyInput,
+            MapParseError::InvalidRun(line) => AocError::Syntax {
+                line,
+                message: "invalid run-length encoding".to_owned(),
+            },
+        }
+    }
+}
+
+impl From<io::Error> for MapParseError {
+    fn from(err: io::Error) -> Self {
+        MapParseError::Io(err)
+    }
+}
+
+// The cells are stored row by row.
+#[derive(Debug, PartialEq)]
+pub struct Map {
+    nrows: usize,
+    ncols: usize,
+    cells: Vec<Cell>,
+}
+
+impl Map {
+    pub fn new(nrows: usize, ncols: usize) -> Self {
+        Map {
+            nrows,
+            ncols,
+            cells: vec![Cell::Open; nrows * ncols],
+        }
+    }
+
+    pub fn from_file(file_name: impl AsRef<Path>) -> Result<Self, MapParseError> {
+        Self::from_reader(File::open(file_name)?)
+    }
+
+    pub fn from_reader(reader: impl Read) -> Result<Self, MapParseError> {
+        aoc::read_all(reader)?.parse()
+    }
+
+    // Builds the map from its rows, as `from_str` does once the text is
+    // split into lines.
+    pub fn from_lines(lines: &[&str]) -> Result<Self, MapParseError> {
+        let ncols = match lines.first() {
+            Some(line) if !line.is_empty() => line.chars().count(),
+            _ => return Err(MapParseError::Empty),
+        };
+        let mut map = Map::new(lines.len(), ncols);
+
+        for (row, line) in lines.iter().enumerate() {
+            let width = line.chars().count();
+            if width != map.ncols {
+                return Err(MapParseError::RaggedRow(row + 1, width, map.ncols));
+            }
+
+            for (col, ch) in line.chars().enumerate() {
+                map.set_cell(MapPosition::new(row, col), Cell::from(ch));
+            }
+        }
+
+        Ok(map)
+    }
+
+    // Encodes each row, one per line, as runs of a count followed by the
+    // cell, such as "2.1#8." for "..#........". Cells that are digits
+    // cannot be told apart from the counts, so such maps do not round trip.
+    pub fn to_rle(&self) -> String {
+        let mut rle = String::new();
+        for row in self.cells.chunks(self.ncols.max(1)) {
+            let mut i = 0;
+            while i < row.len() {
+                let run = row[i..].iter().take_while(|&&cell| cell == row[i]).count();
+                rle.push_str(&run.to_string());
+                rle.push(char::from(row[i]));
+                i += run;
+            }
+            rle.push('\n');
+        }
+        rle
+    }
+
+    // Decodes a map encoded by `to_rle`.
+    pub fn from_rle(s: &str) -> Result<Self, MapParseError> {
+        let mut rows = vec![];
+        for (i, line) in s.lines().enumerate() {
+            let invalid = || MapParseError::InvalidRun(i + 1);
+            let mut row = String::new();
+            let mut run: usize = 0;
+            for ch in line.strip_suffix('\r').unwrap_or(line).chars() {
+                match ch.to_digit(10) {
+                    Some(digit) => {
+                        run = run
+                            .checked_mul(10)
+                            .and_then(|run| run.checked_add(digit as usize))
+                            .ok_or_else(invalid)?;
+                    }
+                    None if run > 0 => {
+                        row.extend(iter::repeat_n(ch, run));
+                        run = 0;
+                    }
+                    None => return Err(invalid()),
+                }
+            }
+            if run > 0 {
+                return Err(invalid());
+            }
+            rows.push(row);
+        }
+
+        let lines: Vec<_> = rows.iter().map(String::as_str).collect();
+        Map::from_lines(&lines)
+    }
+
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.nrows, self.ncols)
+    }
+
+    // Stacks `other` below this map. Both must have as many columns.
+    pub fn concat_vertical(&self, other: &Map) -> Result<Map, DimensionMismatch> {
+        if self.ncols != other.ncols {
+            return Err(self.mismatch(other));
+        }
+
+        let mut cells = self.cells.clone();
+        cells.extend_from_slice(&other.cells);
+        Ok(Map {
+            nrows: self.nrows + other.nrows,
+            ncols: self.ncols,
+            cells,
+        })
+    }
+
+    // Places `other` to the right of this map. Both must have as many rows.
+    pub fn concat_horizontal(&self, other: &Map) -> Result<Map, DimensionMismatch> {
+        if self.nrows != other.nrows {
+            return Err(self.mismatch(other));
+        }
+
+        let mut cells = Vec::with_capacity(self.cells.len() + other.cells.len());
+        for row in 0..self.nrows {
+            cells.extend_from_slice(&self.cells[row * self.ncols..(row + 1) * self.ncols]);
+            cells.extend_from_slice(&other.cells[row * other.ncols..(row + 1) * other.ncols]);
+        }
+        Ok(Map {
+            nrows: self.nrows,
+            ncols: self.ncols + other.ncols,
+            cells,
+        })
+    }
+
+    fn mismatch(&self, other: &Map) -> DimensionMismatch {
+        DimensionMismatch {
+            left: self.dimensions(),
+            right: other.dimensions(),
+        }
+    }
+
+    pub fn add_tree(&mut self, pos: MapPosition) -> Result<(), OutOfBounds> {
+        if pos.row >= self.nrows || pos.col >= self.ncols {
+            return Err(OutOfBounds {
+                pos,
+                dimensions: self.dimensions(),
+            });
+        }
+        self.set_cell(pos, Cell::Tree);
+        Ok(())
+    }
+
+    fn set_cell(&mut self, pos: MapPosition, cell: Cell) {
+        self.cells[pos.row * self.ncols + pos.col] = cell;
+    }
+
+    // The position must be within the map, or a column past the right edge
+    // reads the next row.
+    fn get_cell(&self, pos: MapPosition) -> Cell {
+        self.cells[pos.row * self.ncols + pos.col]
+    }
+
+    // Returns `None` for positions outside of the map, which does not wrap
+    // around here.
+    pub fn cell(&self, pos: MapPosition) -> Option<Cell> {
+        if pos.row < self.nrows && pos.col < self.ncols {
+            Some(self.get_cell(pos))
+        } else {
+            None
+        }
+    }
+
+    // Same as `has_tree`, but positions outside of the map hold no tree.
+    pub fn contains_tree(&self, row: usize, col: usize) -> bool {
+        self.has_tree(MapPosition::new(row, col)) == Some(true)
+    }
+
+    // Returns `None` for positions outside of the map, as `cell` does.
+    pub fn has_tree(&self, pos: MapPosition) -> Option<bool> {
+        self.cell(pos).map(|cell| cell == Cell::Tree)
+    }
+
+    // Counts the trees in rows `r0..r1` and columns `c0..c1`. The bounds are
+    // clamped to the map, rather than wrapping around as the slopes do, so
+    // a rectangle partly or fully outside of it only counts what overlaps.
+    pub fn count_region(&self, r0: usize, c0: usize, r1: usize, c1: usize) -> usize {
+        let (r1, c1) = (r1.min(self.nrows), c1.min(self.ncols));
+        (r0..r1)
+            .flat_map(|row| (c0..c1).map(move |col| MapPosition::new(row, col)))
+            .filter(|&pos| self.get_cell(pos) == Cell::Tree)
+            .count()
+    }
+
+    // Yields each row, from the top, as whether each of its cells holds a
+    // tree.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = bool> + '_> + '_ {
+        (0..self.nrows).map(move |row| {
+            (0..self.ncols).map(move |col| self.get_cell(MapPosition::new(row, col)) == Cell::Tree)
+        })
+    }
+
+    pub fn count_trees_part1(&self) -> usize {
+        self.navigate_toboggan(3, 1)
+    }
+
+    pub fn count_trees_part2(&self) -> Option<u64> {
+        self.slopes_product(&[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)])
+    }
+
+    // The product is computed as u64 whatever the target, and is `None` if
+    // it overflows.
+    pub fn slopes_product(&self, slopes: &[(usize, usize)]) -> Option<u64> {
+        slopes.iter().try_fold(1u64, |product, &(right, down)| {
+            product.checked_mul(self.navigate_toboggan(right, down) as u64)
+        })
+    }
+
+    // Returns the slope hitting the fewest trees, the first one given on a
+    // tie, along with the trees hit, or `None` if there are no candidates.
+    pub fn best_slope(&self, candidates: &[(usize, usize)]) -> Option<((usize, usize), usize)> {
+        candidates
+            .iter()
+            .map(|&(right, down)| ((right, down), self.navigate_toboggan(right, down)))
+            .min_by_key(|&(_, trees)| trees)
+    }
+
+    pub fn navigate_toboggan(&self, right: usize, down: usize) -> usize {
+        self.navigate_stats(right, down).trees
+    }
+
+    // Same as `navigate_toboggan`, but starting from `start` rather than the
+    // top-left corner. As there, a tree at the start counts as hit.
+    pub fn navigate_from(&self, start: MapPosition, right: usize, down: usize) -> usize {
+        self.walk(start, right, down, true)
+            .filter(|(_, hit)| *hit)
+            .count()
+    }
+
+    // Same as `navigate_toboggan`, but for a map that does not repeat to the
+    // right: the traversal ends when running off the right edge.
+    pub fn navigate_no_wrap(&self, right: usize, down: usize) -> usize {
+        self.walk(MapPosition::new(0, 0), right, down, false)
+            .filter(|(_, hit)| *hit)
+            .count()
+    }
+
+    // Counts the cells of each kind visited along the slope, including the
+    // top-left corner.
+    pub fn count_along(&self, right: usize, down: usize) -> HashMap<Cell, usize> {
+        let mut counts = HashMap::new();
+        for (pos, _) in self.path(right, down) {
+            *counts.entry(self.get_cell(pos)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    // Counts the moves down the slope, each descending `down` rows, along
+    // with the trees hit.
+    pub fn navigate_stats(&self, right: usize, down: usize) -> NavStats {
+        let mut stats = NavStats { steps: 0, trees: 0 };
+        for (pos, hit) in self.path(right, down) {
+            if pos.row > 0 {
+                stats.steps += 1;
+            }
+            if hit {
+                stats.trees += 1;
+            }
+        }
+        stats
+    }
+
+    // Counts the rows descended down to the last cell visited along the
+    // slope, and how many times the column wrapped past the right edge.
+    pub fn traversal_report(&self, right: usize, down: usize) -> TraversalReport {
+        let mut report = TraversalReport {
+            rows_descended: 0,
+            times_wrapped: 0,
+            trees_hit: 0,
+        };
+        let mut prev_col = 0;
+        for (pos, hit) in self.path(right, down) {
+            if pos.row > 0 {
+                report.times_wrapped += (prev_col + right) / self.ncols;
+            }
+            report.rows_descended = pos.row;
+            prev_col = pos.col;
+            if hit {
+                report.trees_hit += 1;
+            }
+        }
+        report
+    }
+
+    // Returns the trees hit along the slope, in traversal order.
+    pub fn navigate_collect(&self, right: usize, down: usize) -> Vec<MapPosition> {
+        self.path(right, down)
+            .filter(|(_, hit)| *hit)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    // Draws the map marking each cell visited along the slope with 'O', or
+    // with 'X' if it holds a tree.
+    pub fn render_path(&self, right: usize, down: usize) -> String {
+        let mut grid: Vec<Vec<char>> = (0..self.nrows)
+            .map(|row| {
+                (0..self.ncols)
+                    .map(|col| char::from(self.get_cell(MapPosition::new(row, col))))
+                    .collect()
+            })
+            .collect();
+
+        for (pos, hit) in self.path(right, down) {
+            grid[pos.row][pos.col] = if hit { 'X' } else { 'O' };
+        }
+
+        grid.into_iter()
+            .map(|row| row.into_iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    // Yields each cell visited along the slope, starting at the top-left
+    // corner, and whether it holds a tree. Nothing is visited when `down` is
+    // zero, as the slope would never reach the bottom, or when the map has
+    // no columns.
+    pub fn path(
+        &self,
+        right: usize,
+        down: usize,
+    ) -> impl Iterator<Item = (MapPosition, bool)> + '_ {
+        self.walk(MapPosition::new(0, 0), right, down, true)
+    }
+
+    fn walk(
+        &self,
+        start: MapPosition,
+        right: usize,
+        down: usize,
+        wrap: bool,
+    ) -> impl Iterator<Item = (MapPosition, bool)> + '_ {
+        let start = if down > 0 && self.ncols > 0 {
+            Some(start)
+        } else {
+            None
+        };
+        // Without wrapping, the column is left to grow past the right edge,
+        // and the walk ends if it would overflow.
+        iter::successors(start, move |pos| {
+            if wrap {
+                Some(pos.step(right, down, self.ncols))
+            } else {
+                Some(MapPosition::new(
+                    pos.row.saturating_add(down),
+                    pos.col.checked_add(right)?,
+                ))
+            }
+        })
+        .take_while(move |pos| pos.row < self.nrows && pos.col < self.ncols)
+        .map(move |pos| (pos, self.get_cell(pos) == Cell::Tree))
+    }
+}
+
+impl FromStr for Map {
+    type Err = MapParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `lines` only strips a '\r' followed by '\n', so also drop the one
+        // left on an unterminated last line.
+        let lines: Vec<_> = s
+            .lines()
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+        Map::from_lines(&lines)
+    }
+}
+
+impl Solver for Map {
+    type Input = Map;
+    type Err = MapParseError;
+
+    fn parse(s: &str) -> Result<Self::Input, Self::Err> {
+        s.parse()
+    }
+
+    fn part1(map: &Map) -> String {
+        map.count_trees_part1().to_string()
+    }
+
+    fn part2(map: &Map) -> String {
+        match map.count_trees_part2() {
+            Some(product) => product.to_string(),
+            None => "overflow".to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+    const INPUT: &str = include_str!("input.txt");
+
+    #[test]
+    fn test_example_input() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        assert_eq!(map.count_trees_part1(), 7);
+        assert_eq!(map.count_trees_part2(), Some(336));
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        let map = EXAMPLE.replace('\n', "\r\n").parse::<Map>().unwrap();
+        assert_eq!(map, EXAMPLE.parse::<Map>().unwrap());
+        assert_eq!(map.count_trees_part1(), 7);
+        assert_eq!(map.count_trees_part2(), Some(336));
+
+        let map = "..#\r\n#..\r".parse::<Map>().unwrap();
+        assert_eq!(map.dimensions(), (2, 3));
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn test_gzip_input() {
+        let map = Map::from_file("example.txt.gz").unwrap();
+        assert_eq!(map, EXAMPLE.parse::<Map>().unwrap());
+    }
+
+    #[test]
+    fn test_slopes() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        assert_eq!(map.navigate_toboggan(1, 1), 2);
+        assert_eq!(map.navigate_toboggan(3, 1), 7);
+        assert_eq!(map.navigate_toboggan(5, 1), 3);
+        assert_eq!(map.navigate_toboggan(7, 1), 4);
+        assert_eq!(map.navigate_toboggan(1, 2), 2);
+        assert_eq!(map.slopes_product(&[(3, 1), (1, 2)]), Some(14));
+        assert_eq!(map.slopes_product(&[]), Some(1));
+    }
+
+    #[test]
+    fn test_best_slope() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        assert_eq!(map.best_slope(&slopes), Some(((1, 1), 2)));
+        assert_eq!(map.best_slope(&[(1, 2), (1, 1)]), Some(((1, 2), 2)));
+        assert_eq!(map.best_slope(&[(3, 1), (7, 1), (5, 1)]), Some(((5, 1), 3)));
+        assert_eq!(map.best_slope(&[]), None);
+    }
+
+    #[test]
+    fn test_puzzle_input() {
+        let map = INPUT.parse::<Map>().unwrap();
+        assert_eq!(map.count_trees_part1(), 207);
+        assert_eq!(map.count_trees_part2(), Some(2655892800));
+    }
+
+    #[test]
+    fn test_large_products() {
+        let forest = |nrows| {
+            let mut map = Map::new(nrows, 1);
+            for row in 0..nrows {
+                map.add_tree(MapPosition::new(row, 0)).unwrap();
+            }
+            map
+        };
+
+        let map = forest(300);
+        assert_eq!(map.count_trees_part2(), Some(300 * 300 * 300 * 300 * 150));
+        assert!(map.count_trees_part2().unwrap() > u64::from(u32::MAX));
+
+        let map = forest(10_000);
+        assert_eq!(map.count_trees_part2(), None);
+        assert_eq!(Map::part2(&map), "overflow");
+    }
+
+    #[test]
+    fn test_navigate_no_wrap() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        for &(right, down) in &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)] {
+            assert!(map.navigate_no_wrap(right, down) <= map.navigate_toboggan(right, down));
+        }
+        assert_eq!(map.navigate_no_wrap(1, 1), 2);
+        assert_eq!(map.navigate_no_wrap(3, 1), 1);
+        assert_eq!(map.navigate_no_wrap(20, 1), 0);
+    }
+
+    #[test]
+    fn test_navigate_from() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        let origin = MapPosition::new(0, 0);
+        assert_eq!(map.navigate_from(origin, 3, 1), map.navigate_toboggan(3, 1));
+
+        // From the last column, the first step wraps around to column 2,
+        // then the path continues as if starting from (1, 2).
+        let start = MapPosition::new(0, 10);
+        assert_eq!(map.navigate_from(start, 3, 1), 2);
+        assert_eq!(
+            map.navigate_from(MapPosition::new(1, 2), 3, 1),
+            map.navigate_from(start, 3, 1)
+        );
+
+        // A tree at the start is hit.
+        assert_eq!(map.navigate_from(MapPosition::new(10, 10), 1, 1), 1);
+        assert_eq!(map.navigate_from(MapPosition::new(11, 0), 1, 1), 0);
+    }
+
+    #[test]
+    fn test_navigate_stats() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        assert_eq!(map.navigate_stats(1, 2), NavStats { steps: 5, trees: 2 });
+        assert_eq!(
+            map.navigate_stats(3, 1),
+            NavStats {
+                steps: 10,
+                trees: 7
+            }
+        );
+        assert_eq!(map.navigate_stats(1, 20), NavStats { steps: 0, trees: 0 });
+    }
+
+    #[test]
+    fn test_traversal_report() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        assert_eq!(
+            map.traversal_report(3, 1),
+            TraversalReport {
+                rows_descended: 10,
+                times_wrapped: 2,
+                trees_hit: 7
+            }
+        );
+        assert_eq!(map.traversal_report(1, 2).times_wrapped, 0);
+        assert_eq!(map.traversal_report(1, 2).rows_descended, 10);
+        assert_eq!(map.traversal_report(7, 1).times_wrapped, 6);
+        assert_eq!(map.traversal_report(25, 1).times_wrapped, 22);
+        assert_eq!(map.traversal_report(1, 20).rows_descended, 0);
+    }
+
+    #[test]
+    fn test_navigate_collect() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        let trees: Vec<_> = map
+            .navigate_collect(3, 1)
+            .iter()
+            .map(|pos| (pos.row, pos.col))
+            .collect();
+        assert_eq!(
+            trees,
+            vec![(2, 6), (4, 1), (5, 4), (7, 10), (8, 2), (9, 5), (10, 8)]
+        );
+    }
+
+    #[test]
+    fn test_render_path() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        let expected = "\
+O.##.......
+#..O#...#..
+.#....X..#.
+..#.#...#O#
+.X...##..#.
+..#.X#.....
+.#.#.#.O..#
+.#........X
+#.X#...#...
+#...#X....#
+.#..#...X.#
+";
+        assert_eq!(map.render_path(3, 1), expected);
+    }
+
+    #[test]
+    fn test_path() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        let path: Vec<_> = map
+            .path(1, 2)
+            .map(|(pos, hit)| (pos.row, pos.col, hit))
+            .collect();
+        assert_eq!(
+            path,
+            vec![
+                (0, 0, false),
+                (2, 1, true),
+                (4, 2, false),
+                (6, 3, true),
+                (8, 4, false),
+                (10, 5, false)
+            ]
+        );
+
+        assert_eq!(map.path(3, 0).count(), 0);
+        assert_eq!(map.navigate_toboggan(3, 0), 0);
+        assert_eq!(map.navigate_from(MapPosition::new(1, 1), 3, 0), 0);
+        assert_eq!(map.navigate_no_wrap(3, 0), 0);
+
+        let map = Map::new(2, 0);
+        assert_eq!(map.path(3, 1).count(), 0);
+        assert_eq!(map.navigate_toboggan(3, 1), 0);
+        assert_eq!(map.traversal_report(3, 1).trees_hit, 0);
+        assert_eq!(map.count_trees_part2(), Some(0));
+    }
+
+    #[test]
+    fn test_equality() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        assert_eq!(map.dimensions(), (11, 11));
+
+        let mut built = Map::new(11, 11);
+        for (row, line) in EXAMPLE.lines().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if ch == '#' {
+                    built.add_tree(MapPosition::new(row, col)).unwrap();
+                }
+            }
+        }
+        assert_eq!(built, map);
+
+        built.add_tree(MapPosition::new(0, 0)).unwrap();
+        assert_ne!(built, map);
+        assert_ne!(Map::new(2, 3), Map::new(3, 2));
+    }
+
+    #[test]
+    fn test_rows() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        let text: String = map
+            .rows()
+            .map(|row| {
+                row.map(|tree| if tree { '#' } else { '.' })
+                    .chain(iter::once('\n'))
+                    .collect::<String>()
+            })
+            .collect();
+        assert_eq!(text, EXAMPLE);
+        assert_eq!(Map::new(2, 0).rows().count(), 2);
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let map = Map::from_lines(&[
+            "..##.......",
+            "#...#...#..",
+            ".#....#..#.",
+            "..#.#...#.#",
+            ".#...##..#.",
+            "..#.##.....",
+            ".#.#.#....#",
+            ".#........#",
+            "#.##...#...",
+            "#...##....#",
+            ".#..#...#.#",
+        ])
+        .unwrap();
+        assert_eq!(map, EXAMPLE.parse::<Map>().unwrap());
+        assert_eq!(map.count_trees_part1(), 7);
+
+        let err = Map::from_lines(&["..#", ".#"]).unwrap_err();
+        assert!(matches!(err, MapParseError::RaggedRow(2, 2, 3)));
+        let err = Map::from_lines(&[]).unwrap_err();
+        assert!(matches!(err, MapParseError::Empty));
+    }
+
+    #[test]
+    fn test_parse_from_reader() {
+        let map = Map::from_reader(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(map, EXAMPLE.parse::<Map>().unwrap());
+
+        let err = Map::from_reader(&b"..#\n.#\n"[..]).unwrap_err();
+        assert!(matches!(err, MapParseError::RaggedRow(2, 2, 3)));
+        let err = Map::from_reader(&b""[..]).unwrap_err();
+        assert!(matches!(err, MapParseError::Empty));
+        let err = Map::from_reader(&[0xff, 0xfe][..]).unwrap_err();
+        assert!(matches!(err, MapParseError::Io(_)));
+    }
+
+    #[test]
+    fn test_step() {
+        let pos = MapPosition::new(0, 10);
+        assert_eq!(pos.step(1, 1, 11), MapPosition::new(1, 0));
+        assert_eq!(pos.step(3, 2, 11), MapPosition::new(2, 2));
+        assert_eq!(pos.step(0, 1, 11), MapPosition::new(1, 10));
+        assert_eq!(
+            MapPosition::new(4, 2).step(25, 1, 11),
+            MapPosition::new(5, 5)
+        );
+
+        // usize::MAX is 4 more than a multiple of 11.
+        assert_eq!(pos.step(usize::MAX, 1, 11), MapPosition::new(1, 3));
+        assert_eq!(
+            MapPosition::new(3, usize::MAX).step(usize::MAX, usize::MAX, 11),
+            MapPosition::new(usize::MAX, 8)
+        );
+        assert_eq!(
+            pos.step(usize::MAX - 5, 0, usize::MAX),
+            MapPosition::new(0, 5)
+        );
+
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        assert_eq!(
+            map.navigate_toboggan(usize::MAX, 1),
+            map.navigate_toboggan(4, 1)
+        );
+        assert_eq!(map.navigate_toboggan(3, usize::MAX), 0);
+        assert_eq!(map.navigate_no_wrap(usize::MAX, 1), 0);
+        assert_eq!(
+            map.navigate_from(pos, usize::MAX, 1),
+            map.navigate_from(pos, 4, 1)
+        );
+    }
+
+    #[test]
+    fn test_aoc_error() {
+        let err = AocError::from("..#\n.#.\n#.".parse::<Map>().unwrap_err());
+        assert!(matches!(
+            err,
+            AocError::RaggedMap {
+                line: 3,
+                width: 2,
+                expected: 3
+            }
+        ));
+        assert_eq!(err.to_string(), "line 3: row has 2 columns, expected 3");
+        let err = AocError::from("".parse::<Map>().unwrap_err());
+        assert!(matches!(err, AocError::EmptyInput));
+    }
+
+    #[test]
+    fn test_add_tree_out_of_bounds() {
+        let mut map = Map::new(2, 3);
+        assert!(map.add_tree(MapPosition::new(1, 2)).is_ok());
+        assert!(map.contains_tree(1, 2));
+
+        let err = map.add_tree(MapPosition::new(0, 3)).unwrap_err();
+        assert_eq!(err.pos, MapPosition::new(0, 3));
+        assert_eq!(err.to_string(), "position (0, 3) is outside of the 2x3 map");
+        assert!(map.add_tree(MapPosition::new(2, 0)).is_err());
+        assert_eq!(map, "...\n..#".parse().unwrap());
+
+        assert!(!map.contains_tree(0, 3));
+        assert!(!map.contains_tree(5, 100));
+    }
+
+    #[test]
+    fn test_cell_out_of_bounds() {
+        // A column equal to the width must not read the start of the next
+        // row, which holds a tree.
+        let map = "...\n#..".parse::<Map>().unwrap();
+        assert_eq!(map.cell(MapPosition::new(1, 0)), Some(Cell::Tree));
+        assert_eq!(map.has_tree(MapPosition::new(1, 0)), Some(true));
+        assert_eq!(map.cell(MapPosition::new(0, 3)), None);
+        assert_eq!(map.has_tree(MapPosition::new(0, 3)), None);
+        assert!(!map.contains_tree(0, 3));
+        assert_eq!(map.cell(MapPosition::new(2, 0)), None);
+        assert_eq!(map.has_tree(MapPosition::new(0, 2)), Some(false));
+    }
+
+    #[test]
+    fn test_count_along() {
+        let map = "\
+.#~
+~#.
+#~.
+~.#"
+        .parse::<Map>()
+        .unwrap();
+        assert_eq!(map.cell(MapPosition::new(0, 2)), Some(Cell::Other('~')));
+
+        let counts = map.count_along(1, 1);
+        assert_eq!(counts.get(&Cell::Open), Some(&2));
+        assert_eq!(counts.get(&Cell::Tree), Some(&1));
+        assert_eq!(counts.get(&Cell::Other('~')), Some(&1));
+        assert_eq!(map.navigate_toboggan(1, 1), 1);
+        assert_eq!(map.render_path(1, 1), "O#~\n~X.\n#~O\nO.#\n");
+
+        let counts = map.count_along(2, 1);
+        assert_eq!(counts.get(&Cell::Open), Some(&2));
+        assert_eq!(counts.get(&Cell::Other('~')), Some(&2));
+        assert_eq!(counts.get(&Cell::Tree), None);
+        assert_eq!(map.navigate_toboggan(2, 1), 0);
+    }
+
+    #[test]
+    fn test_count_region() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        assert_eq!(map.count_region(0, 0, 5, 5), 8);
+        assert_eq!(map.count_region(9, 8, 11, 11), 3);
+        assert_eq!(map.count_region(9, 8, 100, 100), 3);
+        assert_eq!(
+            map.count_region(0, 0, 11, 11),
+            map.rows().flatten().filter(|&t| t).count()
+        );
+        assert_eq!(map.count_region(3, 3, 3, 8), 0);
+        assert_eq!(map.count_region(5, 5, 2, 2), 0);
+        assert_eq!(map.count_region(20, 20, 30, 30), 0);
+    }
+
+    #[test]
+    fn test_rle() {
+        let map = EXAMPLE.parse::<Map>().unwrap();
+        let rle = map.to_rle();
+        assert_eq!(rle.lines().next(), Some("2.2#7."));
+        assert_eq!(rle.lines().count(), 11);
+        assert_eq!(Map::from_rle(&rle).unwrap(), map);
+
+        let map = Map::from_rle("12.\r\n1#11.\n").unwrap();
+        assert_eq!(map.dimensions(), (2, 12));
+        assert_eq!(map.has_tree(MapPosition::new(1, 0)), Some(true));
+
+        for invalid in &["3.\n#", "3.\n0.", "3.\n2"] {
+            let err = Map::from_rle(invalid).unwrap_err();
+            assert!(matches!(err, MapParseError::InvalidRun(2)));
+        }
+        let err = Map::from_rle("2.\n3.").unwrap_err();
+        assert!(matches!(err, MapParseError::RaggedRow(2, 3, 2)));
+        assert!(matches!(
+            Map::from_rle("").unwrap_err(),
+            MapParseError::Empty
+        ));
+
+        let err = AocError::from(Map::from_rle("99999999999999999999.").unwrap_err());
+        assert_eq!(err.to_string(), "line 1: invalid run-length encoding");
+    }
+
+    #[test]
+    fn test_concat() {
+        let top = "#..\n.#.".parse::<Map>().unwrap();
+        let bottom = "..#".parse::<Map>().unwrap();
+
+        let map = top.concat_vertical(&bottom).unwrap();
+        assert_eq!(map.dimensions(), (3, 3));
+        assert_eq!(map, "#..\n.#.\n..#".parse().unwrap());
+        assert!(map.contains_tree(0, 0));
+        assert!(map.contains_tree(2, 2));
+        assert!(!map.contains_tree(2, 0));
+
+        let right = "~#\n#.".parse::<Map>().unwrap();
+        let map = top.concat_horizontal(&right).unwrap();
+        assert_eq!(map.dimensions(), (2, 5));
+        assert_eq!(map, "#..~#\n.#.#.".parse().unwrap());
+        assert!(map.contains_tree(0, 4));
+        assert!(map.contains_tree(1, 3));
+        assert_eq!(map.cell(MapPosition::new(0, 3)), Some(Cell::Other('~')));
+
+        let err = top.concat_vertical(&right).unwrap_err();
+        assert_eq!(
+            err,
+            DimensionMismatch {
+                left: (2, 3),
+                right: (2, 2)
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "cannot concatenate a 2x3 map with a 2x2 map"
+        );
+        assert!(top.concat_horizontal(&bottom).is_err());
+    }
+
+    #[test]
+    fn test_solver() {
+        let map = Map::parse("#..\n.#.\n#.#").unwrap();
+        assert_eq!(Map::part1(&map), "2");
+        assert_eq!(Map::part2(&map), "18");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = "..#\n.#.\n#.".parse::<Map>().err().unwrap();
+        assert!(matches!(err, MapParseError::RaggedRow(3, 2, 3)));
+        assert_eq!(err.to_string(), "line 3: row has 2 columns, expected 3");
+
+        let err = "".parse::<Map>().err().unwrap();
+        assert!(matches!(err, MapParseError::Empty));
+    }
+}
@@ -0,0 +1,44 @@
+use aoc::testing::{run, run_with_input, run_with_stdin};
+
+#[test]
+fn test_stdin() {
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_03"),
+        &[],
+        include_bytes!("../example.txt"),
+    );
+    assert_eq!(output, "Result (Part 1): 7\nResult (Part 2): 336\n");
+}
+
+#[test]
+fn test_file() {
+    let output = run(env!("CARGO_BIN_EXE_03"), &["example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1): 7\nResult (Part 2): 336\n"
+    );
+}
+
+#[test]
+fn test_usage() {
+    let output = run(env!("CARGO_BIN_EXE_03"), &["example.txt", "example.txt"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("USAGE: "));
+}
+
+#[test]
+fn test_stdin_without_file() {
+    let output = run_with_input(
+        env!("CARGO_BIN_EXE_03"),
+        &[],
+        include_bytes!("../example.txt"),
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1): 7\nResult (Part 2): 336\n"
+    );
+}
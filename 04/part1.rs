@@ -1,6 +1,5 @@
 use std::collections::HashSet;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -21,7 +20,7 @@ fn validate_passport(passport: &str) -> bool {
 }
 
 fn count_valid_passports(file_name: impl AsRef<Path>) -> usize {
-    fs::read_to_string(file_name)
+    aoc::read_input(file_name)
         .unwrap()
         .split("\n\n")
         .filter(|p| validate_passport(p))
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -98,7 +97,7 @@ fn count_valid_passports(file_name: impl AsRef<Path>) -> usize {
     validators.insert("ecl", validate_ecl);
     validators.insert("pid", validate_pid);
 
-    fs::read_to_string(file_name)
+    aoc::read_input(file_name)
         .unwrap()
         .split("\n\n")
         .filter(|p| validate_passport(p, &validators))
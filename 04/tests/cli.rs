@@ -0,0 +1,53 @@
+use aoc::testing::{run, run_with_stdin};
+
+#[test]
+fn test_stdin_part1() {
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_04-part1"),
+        &[],
+        include_bytes!("../example.txt"),
+    );
+    assert_eq!(output, "Result: 2\n");
+}
+
+#[test]
+fn test_stdin_part2() {
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_04-part2"),
+        &[],
+        include_bytes!("../example.txt"),
+    );
+    assert_eq!(output, "Result: 2\n");
+}
+
+#[test]
+fn test_file_part1() {
+    let output = run(env!("CARGO_BIN_EXE_04-part1"), &["example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "Result: 2\n");
+}
+
+#[test]
+fn test_usage_part1() {
+    let output = run(env!("CARGO_BIN_EXE_04-part1"), &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("USAGE: "));
+}
+
+#[test]
+fn test_file_part2() {
+    let output = run(env!("CARGO_BIN_EXE_04-part2"), &["example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "Result: 2\n");
+}
+
+#[test]
+fn test_usage_part2() {
+    let output = run(env!("CARGO_BIN_EXE_04-part2"), &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("USAGE: "));
+}
@@ -1,5 +1,4 @@
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -31,7 +30,7 @@ fn find_seat(seat: &str, mut lower: usize, mut upper: usize) -> usize {
 }
 
 fn solve(file_name: impl AsRef<Path>) -> (usize, usize) {
-    let content = fs::read_to_string(&file_name).unwrap();
+    let content = aoc::read_input(&file_name).unwrap();
 
     let mut seats = [false; ROWS * COLS];
 
@@ -1,5 +1,4 @@
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -38,17 +37,19 @@ fn count_group_answers_part2(group_answers: &str) -> usize {
         .count()
 }
 
-fn count_answered_part1(file_name: impl AsRef<Path>) -> usize {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn parse_input(file_name: impl AsRef<Path>) -> String {
+    aoc::read_input(file_name).unwrap()
+}
+
+fn count_answered_part1(content: &str) -> usize {
+    content
         .split("\n\n")
         .map(|x| count_group_answers_part1(x))
         .sum()
 }
 
-fn count_answered_part2(file_name: impl AsRef<Path>) -> usize {
-    fs::read_to_string(file_name)
-        .unwrap()
+fn count_answered_part2(content: &str) -> usize {
+    content
         .split("\n\n")
         .map(|x| count_group_answers_part2(x))
         .sum()
@@ -60,8 +61,9 @@ fn main() {
         process::exit(1);
     }
 
-    let part1 = count_answered_part1(env::args().nth(1).unwrap());
-    let part2 = count_answered_part2(env::args().nth(1).unwrap());
+    let content = parse_input(env::args().nth(1).unwrap());
+    let part1 = count_answered_part1(&content);
+    let part2 = count_answered_part2(&content);
     println!("Result (Part 1): {}", part1);
     println!("Result (Part 2): {}", part2);
 }
@@ -72,13 +74,15 @@ mod tests {
 
     #[test]
     fn test_example_input() {
-        assert_eq!(count_answered_part1("example.txt"), 11);
-        assert_eq!(count_answered_part2("example.txt"), 6);
+        let content = parse_input("example.txt");
+        assert_eq!(count_answered_part1(&content), 11);
+        assert_eq!(count_answered_part2(&content), 6);
     }
 
     #[test]
     fn test_puzzle_input() {
-        assert_eq!(count_answered_part1("input.txt"), 6534);
-        assert_eq!(count_answered_part2("input.txt"), 3402);
+        let content = parse_input("input.txt");
+        assert_eq!(count_answered_part1(&content), 6534);
+        assert_eq!(count_answered_part2(&content), 3402);
     }
 }
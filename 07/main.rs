@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -54,7 +53,7 @@ fn parse_input(file_name: impl AsRef<Path>) -> (OuterBags, InnerBagsCount) {
     let re1 = Regex::new(r"(?P<bag>.+) bags contain (?P<inner_bags>.+)\.").unwrap();
     let re2 = Regex::new(r"(?P<count>\d+) (?P<inner_bag>.+?) bags?").unwrap();
 
-    let content = fs::read_to_string(&file_name).unwrap();
+    let content = aoc::read_input(&file_name).unwrap();
     let mut outer_bags = HashMap::new();
     let mut inner_bags_count = HashMap::new();
 
@@ -0,0 +1,61 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use day08::{BootCode, Instruction};
+
+// A program that loops back to its start from the second to last
+// instruction, so that only swapping that jump makes it terminate.
+fn large_program(len: usize) -> BootCode {
+    let mut boot_code = BootCode::new(vec![]);
+    for ip in 0..len - 2 {
+        boot_code.push(match ip % 3 {
+            0 => Instruction::Nop(2),
+            1 => Instruction::Acc(1),
+            _ => Instruction::Jmp(1),
+        });
+    }
+    boot_code.push(Instruction::Jmp(-(len as i32 - 2)));
+    boot_code.push(Instruction::Acc(1));
+    boot_code
+}
+
+fn bench_boot_code(c: &mut Criterion) {
+    let boot_code = include_str!("../input.txt").parse::<BootCode>().unwrap();
+
+    c.bench_function("run", |b| b.iter(|| boot_code.run()));
+    c.bench_function("run_with_fix", |b| b.iter(|| boot_code.run_with_fix()));
+}
+
+fn bench_large_program(c: &mut Criterion) {
+    let boot_code = large_program(50_000);
+    let mut group = c.benchmark_group("large_program");
+    group.sample_size(10);
+
+    group.bench_function("run_with_fix", |b| b.iter(|| boot_code.run_with_fix()));
+    #[cfg(feature = "rayon")]
+    group.bench_function("run_with_fix_parallel", |b| {
+        b.iter(|| boot_code.run_with_fix_parallel())
+    });
+    group.finish();
+}
+
+#[cfg(feature = "testing")]
+fn bench_generated_program(c: &mut Criterion) {
+    let boot_code = BootCode::generate(50_000, 2020, false);
+    let mut group = c.benchmark_group("generated_program");
+    group.sample_size(10);
+
+    group.bench_function("run", |b| b.iter(|| boot_code.run()));
+    group.bench_function("run_with_fix", |b| b.iter(|| boot_code.run_with_fix()));
+    group.finish();
+}
+
+#[cfg(not(feature = "testing"))]
+fn bench_generated_program(_: &mut Criterion) {}
+
+criterion_group!(
+    benches,
+    bench_boot_code,
+    bench_large_program,
+    bench_generated_program
+);
+criterion_main!(benches);
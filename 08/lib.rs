@@ -0,0 +1,2259 @@
+// Without the std feature, only the interpreter and the parsing of text
+// already in memory are built, on top of `core` and `alloc`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use aoc::{AocError, Solver};
+use core::error::Error;
+use core::fmt;
+use core::marker::PhantomData;
+use core::num::ParseIntError;
+use core::ops::Add;
+use core::str::FromStr;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::str;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "op", content = "val", rename_all = "lowercase")
+)]
+pub enum Instruction {
+    Nop(i32),
+    Acc(i32),
+    Jmp(i32),
+}
+
+impl Instruction {
+    pub fn flipped(&self) -> Option<Instruction> {
+        match self {
+            Instruction::Nop(val) => Some(Instruction::Jmp(*val)),
+            Instruction::Jmp(val) => Some(Instruction::Nop(*val)),
+            Instruction::Acc(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Nop(val) => write!(f, "nop {:+}", val),
+            Instruction::Acc(val) => write!(f, "acc {:+}", val),
+            Instruction::Jmp(val) => write!(f, "jmp {:+}", val),
+        }
+    }
+}
+
+// The accumulator of the machine. On top of `Add + Copy + Default`, it needs
+// `From<i32>` to convert the operands and an `add_operand` that returns `None`
+// on overflow, which a plain `Add` has no way to report. There is no default
+// implementation, so each type has to say how it overflows: the i32 and i64
+// ones use `checked_add`, and a saturating wrapper would always return `Some`.
+pub trait Accumulator: Add<Output = Self> + Copy + Default + From<i32> {
+    fn add_operand(self, val: i32) -> Option<Self>;
+}
+
+impl Accumulator for i32 {
+    fn add_operand(self, val: i32) -> Option<Self> {
+        self.checked_add(val)
+    }
+}
+
+impl Accumulator for i64 {
+    fn add_operand(self, val: i32) -> Option<Self> {
+        self.checked_add(i64::from(val))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BootCodeResult<A = i32> {
+    Terminated(A),
+    // The accumulator right before an instruction would execute a second
+    // time, that is after one full pass around the loop.
+    Cyclic(A),
+    OutOfBounds(isize),
+    Exhausted(A),
+    Overflow(usize),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RunStop<A = i32> {
+    Breakpoint(VmState<A>),
+    Terminated(A),
+    Cyclic(A),
+    OutOfBounds(isize),
+    Overflow(usize),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExecState<A = i32> {
+    pub ip: usize,
+    pub acc: A,
+}
+
+// The whole machine state, including which instructions were executed, so
+// that the execution can be resumed exactly where it was left.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VmState<A = i32> {
+    pub ip: usize,
+    pub acc: A,
+    pub visited: Vec<bool>,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    Syntax(usize, SyntaxError),
+}
+
+#[derive(Debug)]
+pub enum SyntaxError {
+    WrongTokenCount(usize),
+    UnknownOpcode(String),
+    InvalidOperand(ParseIntError),
+}
+
+// An error decoding the binary encoding of a program, at the given byte
+// offset.
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    Truncated(usize),
+    UnknownOpcode(usize, u8),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            ParseError::Io(err) => write!(f, "{}", err),
+            ParseError::Syntax(line, err) => write!(f, "line {}: {}", line, err),
+        }
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SyntaxError::WrongTokenCount(count) => {
+                write!(
+                    f,
+                    "expected an opcode and an operand, found {} tokens",
+                    count
+                )
+            }
+            SyntaxError::UnknownOpcode(op) => write!(f, "unknown opcode '{}'", op),
+            SyntaxError::InvalidOperand(err) => write!(f, "invalid operand: {}", err),
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Truncated(offset) => {
+                write!(f, "byte {}: truncated instruction", offset)
+            }
+            DecodeError::UnknownOpcode(offset, op) => {
+                write!(f, "byte {}: unknown opcode {:#04x}", offset, op)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Error for DecodeError {}
+
+impl Error for SyntaxError {}
+
+#[cfg(feature = "std")]
+impl From<ParseError> for AocError {
+    fn from(err: ParseError) -> Self {
+        match err {
+            ParseError::Io(err) => AocError::Io(err),
+            ParseError::Syntax(line, SyntaxError::UnknownOpcode(opcode)) => {
+                AocError::UnknownOpcode { line, opcode }
+            }
+            ParseError::Syntax(line, err) => AocError::Syntax {
+                line,
+                message: err.to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        ParseError::Io(err)
+    }
+}
+
+// An instruction is an opcode and an operand separated by whitespace. The
+// operand is an optional sign followed by one or more decimal digits, leading
+// zeros allowed, and must fit in an i32.
+impl FromStr for Instruction {
+    type Err = SyntaxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split_whitespace().collect();
+        let (op, val) = match parts[..] {
+            [op, val] => (op, val),
+            _ => return Err(SyntaxError::WrongTokenCount(parts.len())),
+        };
+
+        let ins: fn(i32) -> Instruction = match op {
+            "nop" => Instruction::Nop,
+            "acc" => Instruction::Acc,
+            "jmp" => Instruction::Jmp,
+            _ => return Err(SyntaxError::UnknownOpcode(op.to_owned())),
+        };
+        val.parse().map(ins).map_err(SyntaxError::InvalidOperand)
+    }
+}
+
+impl<A: Accumulator> FromStr for GenericBootCode<A> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut instructions = vec![];
+
+        // Comments start with ';' and run to the end of the line. Blank lines
+        // are skipped, but still counted for error messages.
+        for (i, line) in s.lines().enumerate() {
+            let code = line.split(';').next().unwrap_or_default();
+            if code.trim().is_empty() {
+                continue;
+            }
+            let ins = code.parse().map_err(|err| ParseError::Syntax(i + 1, err))?;
+            instructions.push(ins);
+        }
+
+        Ok(GenericBootCode::new(instructions))
+    }
+}
+
+// No single nop/jmp swap makes the program terminate. `considered` counts
+// the nop/jmp instructions executed by the unmodified program, as only those
+// can change its outcome once swapped. `viable` counts the ones among them
+// that jump, once swapped, to where the unmodified program reaches the end,
+// which are the only swaps actually run.
+#[derive(Debug, PartialEq)]
+pub struct NoFix {
+    pub considered: usize,
+    pub viable: usize,
+}
+
+impl fmt::Display for NoFix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "No single nop/jmp swap terminates this program (considered {} executed \
+             nop/jmp instruction{}, {} viable)",
+            self.considered,
+            if self.considered == 1 { "" } else { "s" },
+            self.viable
+        )
+    }
+}
+
+impl Error for NoFix {}
+
+// Only programs with as many instructions can be compared one by one.
+#[derive(Debug, PartialEq)]
+pub struct LengthMismatch {
+    pub left: usize,
+    pub right: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot diff a program of {} instructions with one of {}",
+            self.left, self.right
+        )
+    }
+}
+
+impl Error for LengthMismatch {}
+
+// A structural problem found by `BootCode::validate`, at the given ip.
+#[derive(Debug, PartialEq)]
+pub enum ValidationWarning {
+    EmptyProgram,
+    JumpOutOfBounds(usize, isize),
+    Overflow(usize),
+}
+
+impl ValidationWarning {
+    pub fn ip(&self) -> Option<usize> {
+        match self {
+            ValidationWarning::EmptyProgram => None,
+            ValidationWarning::JumpOutOfBounds(ip, _) | ValidationWarning::Overflow(ip) => {
+                Some(*ip)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationWarning::EmptyProgram => write!(f, "the program is empty"),
+            ValidationWarning::JumpOutOfBounds(ip, target) => {
+                write!(f, "ip {}: jumps out of bounds to {}", ip, target)
+            }
+            ValidationWarning::Overflow(ip) => {
+                write!(f, "ip {}: the accumulator always overflows", ip)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CycleInfo {
+    pub entry: usize,
+    pub instructions: Vec<usize>,
+}
+
+struct Steps<'a, A> {
+    boot_code: &'a GenericBootCode<A>,
+    ip: usize,
+    acc: A,
+    executed: Vec<bool>,
+}
+
+impl<A: Accumulator> Iterator for Steps<'_, A> {
+    type Item = ExecState<A>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ins = self.boot_code.instructions.get(self.ip)?;
+        if self.executed[self.ip] {
+            return None;
+        }
+        self.executed[self.ip] = true;
+
+        if let Instruction::Acc(val) = ins {
+            self.acc = self.acc.add_operand(*val)?;
+        }
+
+        self.ip = self.boot_code.next_ip(self.ip, ins).ok()?;
+        Some(ExecState {
+            ip: self.ip,
+            acc: self.acc,
+        })
+    }
+}
+
+// A program whose accumulator is of type `A`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct GenericBootCode<A> {
+    instructions: Vec<Instruction>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    acc: PhantomData<A>,
+}
+
+pub type BootCode = GenericBootCode<i32>;
+
+impl<A: Accumulator> GenericBootCode<A> {
+    pub fn new(instructions: Vec<Instruction>) -> Self {
+        GenericBootCode {
+            instructions,
+            acc: PhantomData,
+        }
+    }
+
+    pub fn push(&mut self, ins: Instruction) {
+        self.instructions.push(ins);
+    }
+
+    // Jumps are relative, so any of them crossing `index` must be fixed up
+    // by the caller.
+    pub fn insert(&mut self, index: usize, ins: Instruction) {
+        self.instructions.insert(index, ins);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Instruction {
+        self.instructions.remove(index)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Instruction> {
+        self.instructions.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_file(file_name: impl AsRef<Path>) -> Result<Self, ParseError> {
+        Self::from_reader(File::open(file_name)?)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_reader(reader: impl Read) -> Result<Self, ParseError> {
+        aoc::read_all(reader)?.parse()
+    }
+
+    // Parses arbitrary bytes, as a fuzzer produces them. Like `from_reader`,
+    // invalid UTF-8 is reported as an I/O error of kind `InvalidData`.
+    #[cfg(feature = "std")]
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        str::from_utf8(bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+            .parse()
+    }
+
+    // Lists each instruction with its ip and, for jumps, the ip they land
+    // on, or OOB if they jump outside the program.
+    pub fn disassemble(&self) -> String {
+        let mut listing = String::new();
+        for (ip, ins) in self.instructions.iter().enumerate() {
+            let line = match ins {
+                Instruction::Jmp(_) => match self.next_ip(ip, ins) {
+                    Ok(target) => format!("{:04}: {:<9}-> {}", ip, ins.to_string(), target),
+                    Err(_) => format!("{:04}: {:<9}-> OOB", ip, ins.to_string()),
+                },
+                _ => format!("{:04}: {}", ip, ins),
+            };
+            listing.push_str(&line);
+            listing.push('\n');
+        }
+        listing
+    }
+
+    // Serializes the program as an array of `{"op": ..., "val": ...}`
+    // objects.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a program always serializes")
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+
+    // Decodes the binary encoding of `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut instructions = Vec::with_capacity(bytes.len() / 5);
+        for (i, chunk) in bytes.chunks(5).enumerate() {
+            let offset = i * 5;
+            if chunk.len() < 5 {
+                return Err(DecodeError::Truncated(offset));
+            }
+
+            let val = i32::from_le_bytes([chunk[1], chunk[2], chunk[3], chunk[4]]);
+            instructions.push(match chunk[0] {
+                0 => Instruction::Nop(val),
+                1 => Instruction::Acc(val),
+                2 => Instruction::Jmp(val),
+                op => return Err(DecodeError::UnknownOpcode(offset, op)),
+            });
+        }
+        Ok(Self::new(instructions))
+    }
+
+    // Encodes each instruction as one opcode byte, 0 for nop, 1 for acc and
+    // 2 for jmp, followed by the operand as 4 bytes in little-endian order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.instructions.len() * 5);
+        for ins in &self.instructions {
+            let (op, val) = match ins {
+                Instruction::Nop(val) => (0, val),
+                Instruction::Acc(val) => (1, val),
+                Instruction::Jmp(val) => (2, val),
+            };
+            bytes.push(op);
+            bytes.extend_from_slice(&val.to_le_bytes());
+        }
+        bytes
+    }
+
+    // Generates a pseudo-random program of `len` instructions from `seed`,
+    // for benchmarks and tests. Jumps, and nops once swapped, land within
+    // the program, and with `terminating` they only go forward, so that the
+    // program runs to the end.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn generate(len: usize, seed: u64, terminating: bool) -> Self {
+        const MAX_OFFSET: usize = 10;
+
+        // SplitMix64, which keeps the programs reproducible everywhere.
+        let mut state = seed;
+        let mut random = move |bound: u64| {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            (z ^ (z >> 31)) % bound
+        };
+
+        let instructions = (0..len)
+            .map(|ip| {
+                let lo = if terminating {
+                    1
+                } else {
+                    -(ip.min(MAX_OFFSET) as i32)
+                };
+                let hi = (len - ip).min(MAX_OFFSET) as i32;
+                let offset = lo + random((hi - lo + 1) as u64) as i32;
+                match random(3) {
+                    0 => Instruction::Acc(random(101) as i32 - 50),
+                    1 => Instruction::Nop(offset),
+                    _ => Instruction::Jmp(offset),
+                }
+            })
+            .collect();
+        Self::new(instructions)
+    }
+
+    pub fn run(&self) -> BootCodeResult<A> {
+        self.run_with_observer(|_, _, _| ())
+    }
+
+    // Same as `run`, calling `observer` with the ip, the accumulator and the
+    // instruction before each instruction executes.
+    pub fn run_with_observer<F: FnMut(usize, A, &Instruction)>(
+        &self,
+        observer: F,
+    ) -> BootCodeResult<A> {
+        let mut executed = vec![false; self.instructions.len()];
+        self.execute_from(0, A::default(), &mut executed, observer)
+    }
+
+    // Continues the execution from the given ip and accumulator, taking the
+    // instructions marked in `visited` as already executed and marking the
+    // ones executed from now on. `visited` is first resized to the length of
+    // the program, so missing instructions count as not executed yet.
+    pub fn run_from(&self, ip: usize, acc: A, visited: &mut Vec<bool>) -> BootCodeResult<A> {
+        visited.resize(self.instructions.len(), false);
+        self.execute_from(ip, acc, visited, |_, _, _| ())
+    }
+
+    // Captures the machine state after executing `steps` instructions from
+    // the start, or fewer if the program terminates first or the next
+    // instruction would repeat, overflow the accumulator or jump out of
+    // bounds.
+    pub fn snapshot(&self, steps: usize) -> VmState<A> {
+        let mut state = VmState {
+            ip: 0,
+            acc: A::default(),
+            visited: vec![false; self.instructions.len()],
+        };
+
+        for _ in 0..steps {
+            let ins = match self.instructions.get(state.ip) {
+                Some(ins) if !state.visited[state.ip] => ins,
+                _ => break,
+            };
+            let acc = match ins {
+                Instruction::Acc(val) => match state.acc.add_operand(*val) {
+                    Some(acc) => acc,
+                    None => break,
+                },
+                _ => state.acc,
+            };
+            let ip = match self.next_ip(state.ip, ins) {
+                Ok(ip) => ip,
+                Err(_) => break,
+            };
+
+            state.visited[state.ip] = true;
+            state.ip = ip;
+            state.acc = acc;
+        }
+
+        state
+    }
+
+    // Continues the execution from a state returned by `snapshot` or
+    // `run_until`. A hand-built state whose `visited` does not match the
+    // length of the program is resized, as in `run_from`.
+    pub fn resume(&self, mut state: VmState<A>) -> BootCodeResult<A> {
+        self.run_from(state.ip, state.acc, &mut state.visited)
+    }
+
+    // Same as `run`, but detects loops with Floyd's tortoise and hare over
+    // the ip instead of marking every executed instruction.
+    pub fn run_floyd(&self) -> BootCodeResult<A> {
+        match self.find_cycle() {
+            Some((start, length)) => match self.run_bounded(start + length).0 {
+                BootCodeResult::Exhausted(acc) => BootCodeResult::Cyclic(acc),
+                result => result,
+            },
+            None => self.run_bounded(self.instructions.len() + 1).0,
+        }
+    }
+
+    // Stops right before executing an instruction whose ip is in
+    // `breakpoints`, with the machine state from where `resume` continues
+    // exactly as if the program had not stopped.
+    #[cfg(feature = "std")]
+    pub fn run_until(&self, breakpoints: &HashSet<usize>) -> RunStop<A> {
+        let mut executed = vec![false; self.instructions.len()];
+        let mut acc = A::default();
+        let mut ip = 0;
+
+        while ip != self.instructions.len() {
+            if breakpoints.contains(&ip) {
+                return RunStop::Breakpoint(VmState {
+                    ip,
+                    acc,
+                    visited: executed,
+                });
+            }
+
+            if executed[ip] {
+                return RunStop::Cyclic(acc);
+            } else {
+                executed[ip] = true;
+            }
+
+            let ins = &self.instructions[ip];
+            if let Instruction::Acc(val) = ins {
+                acc = match acc.add_operand(*val) {
+                    Some(acc) => acc,
+                    None => return RunStop::Overflow(ip),
+                };
+            }
+
+            ip = match self.next_ip(ip, ins) {
+                Ok(next) => next,
+                Err(target) => return RunStop::OutOfBounds(target),
+            };
+        }
+
+        RunStop::Terminated(acc)
+    }
+
+    // Records the machine state before each instruction executes, until the
+    // program terminates or is about to execute an instruction a second time.
+    pub fn trace(&self) -> Vec<ExecState<A>> {
+        self.execute().0
+    }
+
+    // Formats `trace` as CSV, with a `step,ip,acc,opcode` header and the
+    // accumulator before each instruction executes.
+    pub fn trace_csv(&self) -> String
+    where
+        A: fmt::Display,
+    {
+        let mut csv = "step,ip,acc,opcode\n".to_owned();
+        let mut executed = vec![false; self.instructions.len()];
+        let mut step = 0;
+        self.execute_from(0, A::default(), &mut executed, |ip, acc, ins| {
+            let opcode = match ins {
+                Instruction::Nop(_) => "nop",
+                Instruction::Acc(_) => "acc",
+                Instruction::Jmp(_) => "jmp",
+            };
+            csv.push_str(&format!("{},{},{},{}\n", step, ip, acc, opcode));
+            step += 1;
+        });
+        csv
+    }
+
+    // Yields the machine state after each executed instruction, stopping
+    // when the program terminates, jumps out of bounds, overflows the
+    // accumulator, or is about to execute an instruction a second time.
+    pub fn steps(&self) -> impl Iterator<Item = ExecState<A>> + '_ {
+        Steps {
+            boot_code: self,
+            ip: 0,
+            acc: A::default(),
+            executed: vec![false; self.instructions.len()],
+        }
+    }
+
+    // Returns the accumulator after each executed instruction, as `steps`
+    // yields it.
+    pub fn acc_history(&self) -> Vec<A> {
+        self.steps().map(|state| state.acc).collect()
+    }
+
+    // Lints the program without running it past its first repeated
+    // instruction: jumps that always land outside the program, whichever
+    // way it is reached, and an `acc` that overflows the accumulator on the
+    // way there. Warnings are sorted by instruction, in ascending order.
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        if self.instructions.is_empty() {
+            return vec![ValidationWarning::EmptyProgram];
+        }
+
+        let mut warnings: Vec<_> = self
+            .instructions
+            .iter()
+            .enumerate()
+            .filter(|(_, ins)| matches!(ins, Instruction::Jmp(_)))
+            .filter_map(|(ip, ins)| match self.next_ip(ip, ins) {
+                Ok(_) => None,
+                Err(target) => Some(ValidationWarning::JumpOutOfBounds(ip, target)),
+            })
+            .collect();
+
+        if let BootCodeResult::Overflow(ip) = self.run() {
+            warnings.push(ValidationWarning::Overflow(ip));
+        }
+        warnings.sort_by_key(ValidationWarning::ip);
+        warnings
+    }
+
+    // Returns the lowest and highest values of the accumulator, starting at
+    // 0, until the program stops as with `run`.
+    pub fn acc_extremes(&self) -> (A, A)
+    where
+        A: Ord,
+    {
+        let (mut min, mut max) = (A::default(), A::default());
+        let mut executed = vec![false; self.instructions.len()];
+        let result = self.execute_from(0, A::default(), &mut executed, |_, acc, _| {
+            min = min.min(acc);
+            max = max.max(acc);
+        });
+
+        match result {
+            BootCodeResult::Terminated(acc) | BootCodeResult::Cyclic(acc) => {
+                (min.min(acc), max.max(acc))
+            }
+            _ => (min, max),
+        }
+    }
+
+    // Returns the instructions forming the loop, in execution order, and the
+    // ip at which the loop closes, when the program is cyclic.
+    pub fn cycle_info(&self) -> Option<CycleInfo> {
+        let (trace, result) = self.execute();
+        if !matches!(result, BootCodeResult::Cyclic(_)) {
+            return None;
+        }
+
+        let last = trace.last()?;
+        let entry = self.next_ip(last.ip, &self.instructions[last.ip]).ok()?;
+        let start = trace.iter().position(|s| s.ip == entry)?;
+        Some(CycleInfo {
+            entry,
+            instructions: trace[start..].iter().map(|s| s.ip).collect(),
+        })
+    }
+
+    // Returns the accumulator when the loop is first entered, before its
+    // entry instruction executes for the first time. `Cyclic` instead
+    // reports the accumulator when that instruction is about to repeat.
+    pub fn acc_at_cycle_entry(&self) -> Option<A> {
+        let entry = self.cycle_info()?.entry;
+        self.trace()
+            .iter()
+            .find(|state| state.ip == entry)
+            .map(|state| state.acc)
+    }
+
+    // Returns the instruction that closes the loop, jumping back to an
+    // instruction already executed, when the program is cyclic. It is the
+    // last instruction executed before the first repeat, and a likely typo.
+    pub fn loop_culprit(&self) -> Option<usize> {
+        self.cycle_info()?.instructions.last().copied()
+    }
+
+    // Marks the instructions executed by `run`.
+    pub fn reachable_from_start(&self) -> Vec<bool> {
+        let mut executed = vec![false; self.instructions.len()];
+        self.execute_from(0, A::default(), &mut executed, |_, _, _| ());
+        executed
+    }
+
+    // Returns, in ascending order, the instructions never executed by `run`.
+    pub fn unreachable(&self) -> Vec<usize> {
+        let executed = self.reachable_from_start();
+        (0..executed.len()).filter(|&ip| !executed[ip]).collect()
+    }
+
+    // Executes at most `max_steps` instructions, without stopping at the
+    // first repeated instruction, and returns the result along with the
+    // number of steps taken.
+    pub fn run_bounded(&self, max_steps: usize) -> (BootCodeResult<A>, usize) {
+        let mut acc = A::default();
+        let mut ip = 0;
+
+        for steps in 0..max_steps {
+            let ins = match self.instructions.get(ip) {
+                Some(ins) => ins,
+                None => return (BootCodeResult::Terminated(acc), steps),
+            };
+
+            if let Instruction::Acc(val) = ins {
+                acc = match acc.add_operand(*val) {
+                    Some(acc) => acc,
+                    None => return (BootCodeResult::Overflow(ip), steps),
+                };
+            }
+
+            ip = match self.next_ip(ip, ins) {
+                Ok(next) => next,
+                Err(target) => return (BootCodeResult::OutOfBounds(target), steps + 1),
+            };
+        }
+
+        if ip == self.instructions.len() {
+            (BootCodeResult::Terminated(acc), max_steps)
+        } else {
+            (BootCodeResult::Exhausted(acc), max_steps)
+        }
+    }
+
+    // Counts how many times each instruction executes over at most
+    // `max_steps` steps, without stopping at the first repeated instruction.
+    // Returns early if the program terminates or jumps out of bounds first.
+    pub fn profile(&self, max_steps: usize) -> Vec<u64> {
+        let mut counts = vec![0; self.instructions.len()];
+        let mut ip = 0;
+
+        for _ in 0..max_steps {
+            let ins = match self.instructions.get(ip) {
+                Some(ins) => ins,
+                None => break,
+            };
+            counts[ip] += 1;
+            ip = match self.next_ip(ip, ins) {
+                Ok(next) => next,
+                Err(_) => break,
+            };
+        }
+
+        counts
+    }
+
+    fn execute(&self) -> (Vec<ExecState<A>>, BootCodeResult<A>) {
+        let mut trace = vec![];
+        let mut executed = vec![false; self.instructions.len()];
+        let result = self.execute_from(0, A::default(), &mut executed, |ip, acc, _| {
+            trace.push(ExecState { ip, acc })
+        });
+        (trace, result)
+    }
+
+    fn execute_from(
+        &self,
+        mut ip: usize,
+        mut acc: A,
+        executed: &mut [bool],
+        mut observer: impl FnMut(usize, A, &Instruction),
+    ) -> BootCodeResult<A> {
+        while ip != self.instructions.len() {
+            let ins = match self.instructions.get(ip) {
+                Some(ins) => ins,
+                None => return BootCodeResult::OutOfBounds(ip as isize),
+            };
+
+            if executed[ip] {
+                return BootCodeResult::Cyclic(acc);
+            } else {
+                executed[ip] = true;
+            }
+
+            observer(ip, acc, ins);
+
+            if let Instruction::Acc(val) = ins {
+                acc = match acc.add_operand(*val) {
+                    Some(acc) => acc,
+                    None => return BootCodeResult::Overflow(ip),
+                };
+            }
+
+            ip = match self.next_ip(ip, ins) {
+                Ok(next) => next,
+                Err(target) => return BootCodeResult::OutOfBounds(target),
+            };
+        }
+
+        BootCodeResult::Terminated(acc)
+    }
+
+    // Returns the swapped instruction and the result of the fixed program.
+    // A program that terminates already needs no swap, which is reported
+    // with `None` in place of the instruction.
+    pub fn run_with_fix(&self) -> Option<(Option<usize>, BootCodeResult<A>)> {
+        self.find_fix().ok()
+    }
+
+    // Same as `run_with_fix`, but explains a failure with the number of
+    // executed nop/jmp instructions and how many of them were worth trying.
+    pub fn find_fix(&self) -> Result<(Option<usize>, BootCodeResult<A>), NoFix> {
+        let result = self.run();
+        if let BootCodeResult::Terminated(_) = result {
+            return Ok((None, result));
+        }
+
+        let candidates = self.fix_candidates();
+        candidates
+            .iter()
+            .map(|&ip| (ip, self.patched(ip).run()))
+            .find(|(_, r)| matches!(r, BootCodeResult::Terminated(_)))
+            .map(|(ip, r)| (Some(ip), r))
+            .ok_or_else(|| NoFix {
+                considered: self
+                    .reachable_from_start()
+                    .iter()
+                    .zip(&self.instructions)
+                    .filter(|(&executed, ins)| executed && ins.flipped().is_some())
+                    .count(),
+                viable: candidates.len(),
+            })
+    }
+
+    // Returns the program with the swap found by `run_with_fix` applied, or
+    // unchanged if it terminates already.
+    pub fn apply_fix(&self) -> Option<Self> {
+        match self.run_with_fix()? {
+            (Some(ip), _) => Some(self.patched(ip)),
+            (None, _) => Some(self.clone()),
+        }
+    }
+
+    // Returns, in ascending order, each ip where the instruction differs from
+    // the one in `other`, along with both instructions.
+    pub fn diff(
+        &self,
+        other: &Self,
+    ) -> Result<Vec<(usize, Instruction, Instruction)>, LengthMismatch> {
+        if self.instructions.len() != other.instructions.len() {
+            return Err(LengthMismatch {
+                left: self.instructions.len(),
+                right: other.instructions.len(),
+            });
+        }
+
+        Ok(self
+            .instructions
+            .iter()
+            .zip(&other.instructions)
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(ip, (a, b))| (ip, a.clone(), b.clone()))
+            .collect())
+    }
+
+    // Same as `run_with_fix`, but runs the candidate swaps in parallel. The
+    // lowest swapped instruction that makes the program terminate still wins.
+    #[cfg(feature = "rayon")]
+    pub fn run_with_fix_parallel(&self) -> Option<(Option<usize>, BootCodeResult<A>)>
+    where
+        A: Send + Sync,
+    {
+        let result = self.run();
+        if let BootCodeResult::Terminated(_) = result {
+            return Some((None, result));
+        }
+
+        self.fix_candidates()
+            .into_par_iter()
+            .map(|ip| (ip, self.patched(ip).run()))
+            .find_first(|(_, r)| matches!(r, BootCodeResult::Terminated(_)))
+            .map(|(ip, r)| (Some(ip), r))
+    }
+
+    // Returns every instruction whose nop/jmp swap makes the program
+    // terminate, along with the final accumulator, in ascending order of
+    // instruction rather than the order they are executed in.
+    pub fn all_fixes(&self) -> Vec<(usize, A)> {
+        self.fix_candidates()
+            .into_iter()
+            .filter_map(|ip| match self.patched(ip).run() {
+                BootCodeResult::Terminated(acc) => Some((ip, acc)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Marks, for each instruction, whether its nop/jmp swap makes the
+    // program terminate, as listed by `all_fixes`.
+    pub fn candidate_fixes(&self) -> Vec<bool> {
+        let mut fixes = vec![false; self.instructions.len()];
+        for (ip, _) in self.all_fixes() {
+            fixes[ip] = true;
+        }
+        fixes
+    }
+
+    // An instruction executed from ip 0 is a candidate fix if, once swapped,
+    // it jumps to a position from where the unmodified program reaches the
+    // end. This only holds for a cyclic program, otherwise every nop/jmp is
+    // a candidate. Candidates are returned in ascending order.
+    fn fix_candidates(&self) -> Vec<usize> {
+        let len = self.instructions.len();
+        let reaches_end = self.reaches_end();
+        let mut executed = vec![false; len];
+        let mut candidates = vec![];
+        let mut ip = 0;
+
+        while ip != len && !executed[ip] {
+            executed[ip] = true;
+
+            if let Some(target) = self.instructions[ip]
+                .flipped()
+                .and_then(|ins| self.next_ip(ip, &ins).ok())
+            {
+                if reaches_end[target] {
+                    candidates.push(ip);
+                }
+            }
+
+            match self.next_ip(ip, &self.instructions[ip]) {
+                Ok(next) => ip = next,
+                Err(_) => break,
+            }
+        }
+
+        if ip == len {
+            return (0..len)
+                .filter(|&ip| self.instructions[ip].flipped().is_some())
+                .collect();
+        }
+
+        candidates.sort_unstable();
+        candidates
+    }
+
+    // Searches the combinations of up to `max_swaps` nop/jmp swaps, fewest
+    // swaps first, for one that makes the program terminate. Returns the
+    // swapped instructions in ascending order and the final accumulator.
+    pub fn min_swaps_to_terminate(&self, max_swaps: usize) -> Option<(Vec<usize>, A)> {
+        let swappable: Vec<usize> = (0..self.instructions.len())
+            .filter(|&ip| self.instructions[ip].flipped().is_some())
+            .collect();
+
+        for k in 0..=max_swaps.min(swappable.len()) {
+            // Indices into `swappable` of the current combination, advanced
+            // in lexicographic order.
+            let mut combination: Vec<usize> = (0..k).collect();
+            loop {
+                let mut patched = self.clone();
+                for &i in &combination {
+                    patched = patched.patched(swappable[i]);
+                }
+                if let BootCodeResult::Terminated(acc) = patched.run() {
+                    return Some((combination.iter().map(|&i| swappable[i]).collect(), acc));
+                }
+
+                match (0..k)
+                    .rev()
+                    .find(|&i| combination[i] != i + swappable.len() - k)
+                {
+                    Some(i) => {
+                        combination[i] += 1;
+                        for j in i + 1..k {
+                            combination[j] = combination[j - 1] + 1;
+                        }
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        None
+    }
+
+    fn patched(&self, ip: usize) -> Self {
+        let mut new_instructions = self.instructions.clone();
+        if let Some(fixed) = new_instructions[ip].flipped() {
+            new_instructions[ip] = fixed;
+        }
+        Self::new(new_instructions)
+    }
+
+    // Returns the position executed after `ins`, or the attempted position
+    // if it falls outside the program. Landing right after the last
+    // instruction is in bounds, as that is how a program terminates.
+    fn next_ip(&self, ip: usize, ins: &Instruction) -> Result<usize, isize> {
+        let next = match ins {
+            Instruction::Jmp(val) => ip as isize + *val as isize,
+            _ => ip as isize + 1,
+        };
+
+        if next >= 0 && next as usize <= self.instructions.len() {
+            Ok(next as usize)
+        } else {
+            Err(next)
+        }
+    }
+
+    // Returns the number of steps before the execution enters a loop and the
+    // length of the loop, or `None` if the program stops without looping.
+    fn find_cycle(&self) -> Option<(usize, usize)> {
+        let next = |ip: usize| {
+            self.instructions
+                .get(ip)
+                .and_then(|ins| self.next_ip(ip, ins).ok())
+        };
+
+        let mut tortoise = next(0)?;
+        let mut hare = next(tortoise)?;
+        while tortoise != hare {
+            tortoise = next(tortoise)?;
+            hare = next(next(hare)?)?;
+        }
+
+        let mut start = 0;
+        tortoise = 0;
+        while tortoise != hare {
+            tortoise = next(tortoise)?;
+            hare = next(hare)?;
+            start += 1;
+        }
+
+        let mut length = 1;
+        hare = next(tortoise)?;
+        while tortoise != hare {
+            hare = next(hare)?;
+            length += 1;
+        }
+
+        Some((start, length))
+    }
+
+    // Marks, for each position including the end, whether the unmodified
+    // program reaches the end when started from there.
+    fn reaches_end(&self) -> Vec<bool> {
+        let len = self.instructions.len();
+        let mut predecessors = vec![vec![]; len + 1];
+        for (ip, ins) in self.instructions.iter().enumerate() {
+            if let Ok(next) = self.next_ip(ip, ins) {
+                predecessors[next].push(ip);
+            }
+        }
+
+        let mut reaches_end = vec![false; len + 1];
+        let mut stack = vec![len];
+        reaches_end[len] = true;
+        while let Some(ip) = stack.pop() {
+            for &prev in &predecessors[ip] {
+                if !reaches_end[prev] {
+                    reaches_end[prev] = true;
+                    stack.push(prev);
+                }
+            }
+        }
+
+        reaches_end
+    }
+}
+
+#[cfg(feature = "std")]
+impl Solver for BootCode {
+    type Input = BootCode;
+    type Err = ParseError;
+
+    fn parse(s: &str) -> Result<Self::Input, Self::Err> {
+        s.parse()
+    }
+
+    fn part1(boot_code: &BootCode) -> String {
+        format!("{:?}", boot_code.run())
+    }
+
+    fn part2(boot_code: &BootCode) -> String {
+        match boot_code.find_fix() {
+            Ok((_, result)) => format!("{:?}", result),
+            Err(err) => err.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+    const INPUT: &str = include_str!("input.txt");
+
+    fn run_with_fix_brute_force(boot_code: &BootCode) -> Option<(Option<usize>, BootCodeResult)> {
+        let r = boot_code.run();
+        if let BootCodeResult::Terminated(_) = r {
+            return Some((None, r));
+        }
+
+        for ip in 0..boot_code.instructions.len() {
+            if boot_code.instructions[ip].flipped().is_none() {
+                continue;
+            }
+
+            let r = boot_code.patched(ip).run();
+            if let BootCodeResult::Terminated(_) = r {
+                return Some((Some(ip), r));
+            }
+        }
+
+        None
+    }
+
+    #[test]
+    fn test_example_input() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(5));
+        assert_eq!(
+            boot_code.run_with_fix(),
+            Some((Some(7), BootCodeResult::Terminated(8)))
+        );
+    }
+
+    #[test]
+    fn test_puzzle_input() {
+        let boot_code = INPUT.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(1810));
+        assert_eq!(
+            boot_code.run_with_fix(),
+            Some((Some(313), BootCodeResult::Terminated(969)))
+        );
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        let boot_code = EXAMPLE.replace('\n', "\r\n").parse::<BootCode>().unwrap();
+        assert_eq!(boot_code, EXAMPLE.parse::<BootCode>().unwrap());
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(5));
+        assert_eq!(
+            boot_code.run_with_fix(),
+            Some((Some(7), BootCodeResult::Terminated(8)))
+        );
+
+        let err = "nop +0\r\nacc\r\n".parse::<BootCode>().unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::Syntax(2, SyntaxError::WrongTokenCount(1))
+        ));
+    }
+
+    #[test]
+    fn test_fix_matches_brute_force() {
+        for content in &[EXAMPLE, INPUT] {
+            let boot_code = content.parse::<BootCode>().unwrap();
+            assert_eq!(
+                boot_code.run_with_fix(),
+                run_with_fix_brute_force(&boot_code)
+            );
+        }
+
+        let boot_code = "nop +1\nacc +2\njmp +1".parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.run_with_fix(),
+            run_with_fix_brute_force(&boot_code)
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(
+            BootCode::generate(100, 7, false),
+            BootCode::generate(100, 7, false)
+        );
+        assert_ne!(
+            BootCode::generate(100, 7, false),
+            BootCode::generate(100, 8, false)
+        );
+        assert!(BootCode::generate(0, 7, true).is_empty());
+
+        for seed in 0..50 {
+            let boot_code = BootCode::generate(200, seed, true);
+            assert_eq!(boot_code.len(), 200);
+            assert!(matches!(boot_code.run(), BootCodeResult::Terminated(_)));
+
+            let boot_code = BootCode::generate(200, seed, false);
+            assert!(!matches!(
+                boot_code.run(),
+                BootCodeResult::OutOfBounds(_) | BootCodeResult::Overflow(_)
+            ));
+            assert_eq!(
+                boot_code.run_with_fix(),
+                run_with_fix_brute_force(&boot_code)
+            );
+        }
+    }
+
+    // Programs built from small operands, which proptest shrinks towards
+    // fewer instructions and operands closer to zero.
+    fn program() -> impl Strategy<Value = BootCode> {
+        let instruction = prop_oneof![
+            (-50..=50).prop_map(Instruction::Acc),
+            (-10..=10).prop_map(Instruction::Nop),
+            (-10..=10).prop_map(Instruction::Jmp),
+        ];
+        prop::collection::vec(instruction, 0..64).prop_map(BootCode::new)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_fix_terminates(boot_code in program()) {
+            let fix = boot_code.run_with_fix();
+            prop_assert_eq!(&fix, &run_with_fix_brute_force(&boot_code));
+
+            if let Some((_, result)) = fix {
+                prop_assert!(matches!(result, BootCodeResult::Terminated(_)));
+                prop_assert_eq!(boot_code.apply_fix().unwrap().run(), result);
+            } else {
+                prop_assert_eq!(boot_code.apply_fix(), None);
+            }
+        }
+
+        #[test]
+        fn prop_run_stops(boot_code in program()) {
+            // An instruction never runs twice, so the program stops within
+            // as many steps as it has instructions.
+            prop_assert!(boot_code.trace().len() <= boot_code.len());
+
+            let (bounded, _) = boot_code.run_bounded(boot_code.len() + 1);
+            match boot_code.run() {
+                BootCodeResult::Cyclic(_) => {
+                    prop_assert!(matches!(bounded, BootCodeResult::Exhausted(_)))
+                }
+                result => prop_assert_eq!(bounded, result),
+            }
+        }
+
+        #[test]
+        fn prop_format_round_trip(boot_code in program()) {
+            let formatted = format_program(&boot_code);
+            let parsed = formatted.parse::<BootCode>().unwrap();
+            prop_assert_eq!(format_program(&parsed), formatted);
+            prop_assert_eq!(parsed, boot_code);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_run_with_fix_parallel() {
+        let programs = [EXAMPLE, INPUT, "nop +1\nacc +2\njmp +1", "jmp +0\njmp -1"];
+        for content in &programs {
+            let boot_code = content.parse::<BootCode>().unwrap();
+            assert_eq!(boot_code.run_with_fix_parallel(), boot_code.run_with_fix());
+        }
+    }
+
+    #[test]
+    fn test_out_of_bounds_jump() {
+        let boot_code = "jmp -10\nacc +1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::OutOfBounds(-10));
+
+        let boot_code = "acc +1\njmp +99\nacc +1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::OutOfBounds(100));
+
+        let boot_code = "acc +1\njmp +2\nacc +1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::Terminated(1));
+    }
+
+    #[test]
+    fn test_run_from() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let mut visited = vec![false; boot_code.len()];
+        assert_eq!(
+            boot_code.run_from(boot_code.len(), 3, &mut visited),
+            BootCodeResult::Terminated(3)
+        );
+        assert_eq!(
+            boot_code.run_from(8, 2, &mut visited),
+            BootCodeResult::Terminated(8)
+        );
+        assert_eq!(
+            boot_code.run_from(1, 0, &mut visited),
+            BootCodeResult::Cyclic(5)
+        );
+        assert_eq!(
+            visited,
+            vec![false, true, true, true, true, false, true, true, true]
+        );
+
+        let mut visited = vec![false; boot_code.len()];
+        visited[1] = true;
+        assert_eq!(
+            boot_code.run_from(0, 0, &mut visited),
+            BootCodeResult::Cyclic(0)
+        );
+
+        let mut visited = vec![];
+        assert_eq!(
+            boot_code.run_from(0, 0, &mut visited),
+            BootCodeResult::Cyclic(5)
+        );
+        assert_eq!(visited.len(), boot_code.len());
+
+        let mut visited = vec![true; 20];
+        assert_eq!(
+            boot_code.run_from(8, 2, &mut visited),
+            BootCodeResult::Cyclic(2)
+        );
+        assert_eq!(visited, vec![true; boot_code.len()]);
+    }
+
+    #[test]
+    fn test_run_with_observer() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let mut log = vec![];
+        let result = boot_code.run_with_observer(|ip, acc, ins| {
+            log.push(format!("{} {} {}", ip, acc, ins));
+        });
+        assert_eq!(result, BootCodeResult::Cyclic(5));
+        assert_eq!(
+            log,
+            vec![
+                "0 0 nop +0",
+                "1 0 acc +1",
+                "2 1 jmp +4",
+                "6 1 acc +1",
+                "7 2 jmp -4",
+                "3 2 acc +3",
+                "4 5 jmp -3"
+            ]
+        );
+
+        let mut jumps = 0;
+        let boot_code = INPUT.parse::<BootCode>().unwrap();
+        boot_code.run_with_observer(|_, _, ins| {
+            if let Instruction::Jmp(_) = ins {
+                jumps += 1;
+            }
+        });
+        assert_eq!(
+            jumps,
+            boot_code
+                .trace()
+                .iter()
+                .filter(|s| matches!(boot_code.get(s.ip), Some(Instruction::Jmp(_))))
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_run_floyd() {
+        for content in &[EXAMPLE, INPUT] {
+            let boot_code = content.parse::<BootCode>().unwrap();
+            assert_eq!(boot_code.run_floyd(), boot_code.run());
+            assert_eq!(boot_code.patched(7).run_floyd(), boot_code.patched(7).run());
+        }
+
+        for program in &[
+            "",
+            "jmp +0",
+            "acc +1\njmp -10",
+            "acc +2000000000\nacc +2000000000",
+        ] {
+            let boot_code = program.parse::<BootCode>().unwrap();
+            assert_eq!(boot_code.run_floyd(), boot_code.run());
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let state = boot_code.snapshot(3);
+        assert_eq!((state.ip, state.acc), (6, 1));
+        assert_eq!(
+            state.visited,
+            vec![true, true, true, false, false, false, false, false, false]
+        );
+        assert_eq!(boot_code.snapshot(100), boot_code.snapshot(7));
+
+        for content in &[EXAMPLE, INPUT] {
+            let boot_code = content.parse::<BootCode>().unwrap();
+            for steps in 0..=boot_code.len() {
+                let state = boot_code.snapshot(steps);
+                assert_eq!(boot_code.resume(state), boot_code.run());
+            }
+        }
+
+        for program in &[
+            "acc +1\njmp -5",
+            "acc +2000000000\nacc +2000000000",
+            "acc +1",
+        ] {
+            let boot_code = program.parse::<BootCode>().unwrap();
+            assert_eq!(boot_code.resume(boot_code.snapshot(5)), boot_code.run());
+        }
+    }
+
+    #[test]
+    fn test_run_until() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let breakpoints: HashSet<_> = vec![1].into_iter().collect();
+        let mut visited = vec![false; boot_code.len()];
+        visited[0] = true;
+        assert_eq!(
+            boot_code.run_until(&breakpoints),
+            RunStop::Breakpoint(VmState {
+                ip: 1,
+                acc: 0,
+                visited
+            })
+        );
+
+        let breakpoints: HashSet<_> = vec![3, 8].into_iter().collect();
+        match boot_code.run_until(&breakpoints) {
+            RunStop::Breakpoint(state) => {
+                assert_eq!((state.ip, state.acc), (3, 2));
+                assert_eq!(boot_code.resume(state), BootCodeResult::Cyclic(5));
+            }
+            stop => panic!("unexpected stop: {:?}", stop),
+        }
+        assert_eq!(boot_code.run_until(&HashSet::new()), RunStop::Cyclic(5));
+
+        let boot_code = "acc +1
+jmp +2
+acc +1"
+            .parse::<BootCode>()
+            .unwrap();
+        let breakpoints: HashSet<_> = vec![2].into_iter().collect();
+        assert_eq!(boot_code.run_until(&breakpoints), RunStop::Terminated(1));
+    }
+
+    #[test]
+    fn test_resume_breakpoint() {
+        for content in &[EXAMPLE, INPUT, "jmp +2\nacc +1\njmp -2"] {
+            let boot_code = content.parse::<BootCode>().unwrap();
+            let mut stops = 0;
+            for ip in 0..boot_code.len() {
+                let breakpoints: HashSet<_> = vec![ip].into_iter().collect();
+                if let RunStop::Breakpoint(state) = boot_code.run_until(&breakpoints) {
+                    assert_eq!(state.ip, ip);
+                    assert_eq!(boot_code.resume(state), boot_code.run());
+                    stops += 1;
+                }
+            }
+            assert_eq!(
+                stops,
+                boot_code
+                    .reachable_from_start()
+                    .iter()
+                    .filter(|&&r| r)
+                    .count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_resume_mismatched_visited() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        for len in &[0, 3, boot_code.len() + 4] {
+            let state = VmState {
+                ip: 0,
+                acc: 0,
+                visited: vec![false; *len],
+            };
+            assert_eq!(boot_code.resume(state), boot_code.run());
+        }
+
+        let state = VmState {
+            ip: 2,
+            acc: 0,
+            visited: vec![true, true],
+        };
+        assert_eq!(boot_code.resume(state), BootCodeResult::Cyclic(4));
+    }
+
+    #[test]
+    fn test_trace() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let trace = boot_code.trace();
+        let states: Vec<_> = trace.iter().map(|s| (s.ip, s.acc)).collect();
+        assert_eq!(
+            states,
+            vec![(0, 0), (1, 0), (2, 1), (6, 1), (7, 2), (3, 2), (4, 5)]
+        );
+    }
+
+    #[test]
+    fn test_steps() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let states: Vec<_> = boot_code.steps().map(|s| (s.ip, s.acc)).collect();
+        assert_eq!(
+            states,
+            vec![(1, 0), (2, 1), (6, 1), (7, 2), (3, 2), (4, 5), (1, 5)]
+        );
+
+        let boot_code = "acc +1\nacc +2".parse::<BootCode>().unwrap();
+        let last = boot_code.steps().last().unwrap();
+        assert_eq!(last, ExecState { ip: 2, acc: 3 });
+    }
+
+    #[test]
+    fn test_acc_history() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.acc_history(), vec![0, 1, 1, 2, 2, 5, 5]);
+
+        let boot_code = "acc +1\nacc -3".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.acc_history(), vec![1, -2]);
+        assert!(BootCode::new(vec![]).acc_history().is_empty());
+    }
+
+    #[test]
+    fn test_validate() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.validate(), vec![]);
+
+        let warnings = BootCode::new(vec![]).validate();
+        assert_eq!(warnings, vec![ValidationWarning::EmptyProgram]);
+        assert_eq!(warnings[0].ip(), None);
+        assert_eq!(warnings[0].to_string(), "the program is empty");
+
+        let boot_code = "jmp +2\njmp -5\nnop -9\njmp +3\nacc +1"
+            .parse::<BootCode>()
+            .unwrap();
+        let warnings = boot_code.validate();
+        assert_eq!(
+            warnings,
+            vec![
+                ValidationWarning::JumpOutOfBounds(1, -4),
+                ValidationWarning::JumpOutOfBounds(3, 6)
+            ]
+        );
+        assert_eq!(warnings[0].ip(), Some(1));
+        assert_eq!(warnings[0].to_string(), "ip 1: jumps out of bounds to -4");
+
+        let boot_code = "acc +2000000000\nacc +2000000000\njmp +9"
+            .parse::<BootCode>()
+            .unwrap();
+        assert_eq!(
+            boot_code.validate(),
+            vec![
+                ValidationWarning::Overflow(1),
+                ValidationWarning::JumpOutOfBounds(2, 11)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_acc_extremes() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.acc_extremes(), (0, 5));
+
+        let boot_code = "acc -3\nacc +5\nacc -1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.acc_extremes(), (-3, 2));
+        let boot_code = "acc +7\njmp -1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.acc_extremes(), (0, 7));
+        assert_eq!(BootCode::new(vec![]).acc_extremes(), (0, 0));
+    }
+
+    #[test]
+    fn test_cycle_info() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let info = boot_code.cycle_info().unwrap();
+        assert_eq!(info.entry, 1);
+        assert_eq!(info.instructions, vec![1, 2, 6, 7, 3, 4]);
+
+        let boot_code = "acc +1\njmp +1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.cycle_info(), None);
+    }
+
+    #[test]
+    fn test_acc_at_cycle_entry() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.acc_at_cycle_entry(), Some(0));
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(5));
+
+        let boot_code = "acc +2\nacc +3\njmp -1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.acc_at_cycle_entry(), Some(2));
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(5));
+
+        let boot_code = "acc +1\njmp +1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.acc_at_cycle_entry(), None);
+    }
+
+    #[test]
+    fn test_loop_culprit() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.loop_culprit(), Some(4));
+        assert_eq!(boot_code.instructions[4], Instruction::Jmp(-3));
+
+        let boot_code = "acc +2\nacc +3\njmp -1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.loop_culprit(), Some(2));
+
+        let boot_code = "acc +1\njmp +1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.loop_culprit(), None);
+    }
+
+    #[test]
+    fn test_run_bounded() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.run_bounded(13),
+            (BootCodeResult::Exhausted(10), 13)
+        );
+
+        let boot_code = "acc +1\nacc +2".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run_bounded(2), (BootCodeResult::Terminated(3), 2));
+        assert_eq!(
+            boot_code.run_bounded(10),
+            (BootCodeResult::Terminated(3), 2)
+        );
+        assert_eq!(boot_code.run_bounded(1), (BootCodeResult::Exhausted(1), 1));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let boot_code = "acc +2000000000\nacc +2000000000"
+            .parse::<BootCode>()
+            .unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::Overflow(1));
+
+        let boot_code = "acc +2000000000\njmp -1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(2000000000));
+        assert_eq!(boot_code.run_bounded(10), (BootCodeResult::Overflow(0), 2));
+
+        let boot_code = "acc -2000000000\njmp -1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run_bounded(10), (BootCodeResult::Overflow(0), 2));
+    }
+
+    #[test]
+    fn test_add_operand() {
+        assert_eq!(1i32.add_operand(2), Some(3));
+        assert_eq!(i32::MAX.add_operand(1), None);
+        assert_eq!(i32::MIN.add_operand(-1), None);
+        assert_eq!(
+            i64::from(i32::MAX).add_operand(1),
+            Some(i64::from(i32::MAX) + 1)
+        );
+        assert_eq!(i64::MAX.add_operand(1), None);
+    }
+
+    #[test]
+    fn test_i64_accumulator() {
+        let content = "acc +2000000000\nacc +2000000000\njmp +2\njmp -3";
+        let boot_code = content.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::Overflow(1));
+
+        let boot_code = content.parse::<GenericBootCode<i64>>().unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::Terminated(4_000_000_000));
+        assert_eq!(boot_code.acc_extremes(), (0, 4_000_000_000));
+        assert_eq!(
+            boot_code.run_with_fix(),
+            Some((None, BootCodeResult::Terminated(4_000_000_000)))
+        );
+    }
+
+    #[test]
+    fn test_profile() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.profile(13), vec![1, 2, 2, 2, 2, 0, 2, 2, 0]);
+
+        let boot_code = "acc +1\nacc +2".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.profile(1_000_000), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_reachable_from_start() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.reachable_from_start(),
+            vec![true, true, true, true, true, false, true, true, false]
+        );
+
+        let boot_code = "jmp +2\nacc +1\njmp +5".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.reachable_from_start(), vec![true, false, true]);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.unreachable(), vec![5, 8]);
+    }
+
+    #[test]
+    fn test_all_fixes() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.all_fixes(), vec![(7, 8)]);
+
+        let boot_code = "nop +3\nnop +2\njmp -2\nacc +1"
+            .parse::<BootCode>()
+            .unwrap();
+        assert_eq!(boot_code.all_fixes(), vec![(0, 1), (1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn test_all_fixes_order() {
+        // Executes 0, 3, 1 and 2, so the fix at 3 is found before the one at 1.
+        let boot_code = "jmp +3\nnop +4\njmp -2\njmp -2\nacc +1"
+            .parse::<BootCode>()
+            .unwrap();
+        for _ in 0..10 {
+            assert_eq!(boot_code.all_fixes(), vec![(1, 0), (3, 1)]);
+        }
+        assert_eq!(
+            boot_code.candidate_fixes(),
+            vec![false, true, false, true, false]
+        );
+    }
+
+    #[test]
+    fn test_candidate_fixes() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let mut expected = vec![false; 9];
+        expected[7] = true;
+        assert_eq!(boot_code.candidate_fixes(), expected);
+
+        let boot_code = "nop +3\nnop +2\njmp -2\nacc +1"
+            .parse::<BootCode>()
+            .unwrap();
+        assert_eq!(boot_code.candidate_fixes(), vec![true, true, true, false]);
+        assert!(BootCode::new(vec![]).candidate_fixes().is_empty());
+    }
+
+    #[test]
+    fn test_build_program() {
+        let mut boot_code = BootCode::new(vec![]);
+        assert!(boot_code.is_empty());
+
+        boot_code.push(Instruction::Acc(3));
+        boot_code.push(Instruction::Jmp(-1));
+        boot_code.insert(0, Instruction::Nop(0));
+        assert_eq!(boot_code.len(), 3);
+        assert_eq!(boot_code.get(1).unwrap().to_string(), "acc +3");
+        assert!(boot_code.get(3).is_none());
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(3));
+
+        assert_eq!(boot_code.remove(2).to_string(), "jmp -1");
+        assert_eq!(boot_code.run(), BootCodeResult::Terminated(3));
+    }
+
+    #[test]
+    fn test_solver() {
+        let boot_code = BootCode::parse("nop +0\nacc +1\njmp -2").unwrap();
+        assert_eq!(BootCode::part1(&boot_code), "Cyclic(1)");
+        assert_eq!(BootCode::part2(&boot_code), "Terminated(1)");
+
+        let boot_code = BootCode::parse("jmp +0\njmp -1").unwrap();
+        assert_eq!(
+            BootCode::part2(&boot_code),
+            "No single nop/jmp swap terminates this program (considered 1 executed \
+             nop/jmp instruction, 0 viable)"
+        );
+    }
+
+    #[test]
+    fn test_parse_from_str() {
+        let boot_code = "nop +0\nacc +3\njmp -2".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(3));
+    }
+
+    #[test]
+    fn test_patch_leaves_original() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let original = boot_code.clone();
+        let patched = boot_code.patched(7);
+        assert_eq!(boot_code, original);
+        assert_ne!(patched, original);
+        assert_eq!(patched.get(7), Some(&Instruction::Nop(-4)));
+        assert_eq!(patched.patched(7), original);
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.disassemble(),
+            include_str!("example-disassembly.txt")
+        );
+
+        let boot_code = "jmp -1\njmp +1\njmp +2".parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.disassemble(),
+            "0000: jmp -1   -> OOB\n0001: jmp +1   -> 2\n0002: jmp +2   -> OOB\n"
+        );
+    }
+
+    #[test]
+    fn test_trace_csv() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let csv = boot_code.trace_csv();
+        assert_eq!(csv.lines().count(), boot_code.trace().len() + 1);
+        assert_eq!(
+            csv.lines().take(4).collect::<Vec<_>>(),
+            vec!["step,ip,acc,opcode", "0,0,0,nop", "1,1,0,acc", "2,2,1,jmp"]
+        );
+        assert_eq!(csv.lines().last(), Some("6,4,5,jmp"));
+
+        assert_eq!(BootCode::new(vec![]).trace_csv(), "step,ip,acc,opcode\n");
+        let boot_code = "acc -3\njmp +2".parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.trace_csv(),
+            "step,ip,acc,opcode\n0,0,0,acc\n1,1,-3,jmp\n"
+        );
+    }
+
+    #[test]
+    fn test_min_swaps_to_terminate() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.min_swaps_to_terminate(0), None);
+        assert_eq!(boot_code.min_swaps_to_terminate(3), Some((vec![7], 8)));
+
+        let boot_code = "jmp +0\nacc +1\njmp +0\nacc +2"
+            .parse::<BootCode>()
+            .unwrap();
+        assert_eq!(boot_code.run_with_fix(), None);
+        assert_eq!(boot_code.min_swaps_to_terminate(1), None);
+        assert_eq!(boot_code.min_swaps_to_terminate(2), Some((vec![0, 2], 3)));
+
+        let boot_code = "acc +1\nnop +0".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.min_swaps_to_terminate(2), Some((vec![], 1)));
+    }
+
+    #[test]
+    fn test_apply_fix() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let fixed = boot_code.apply_fix().unwrap();
+        assert_eq!(fixed.get(7), Some(&Instruction::Nop(-4)));
+        assert_eq!(fixed.run(), BootCodeResult::Terminated(8));
+        assert_eq!(boot_code.get(7), Some(&Instruction::Jmp(-4)));
+
+        let boot_code = "acc +1\njmp +1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.apply_fix(), Some(boot_code.clone()));
+
+        let boot_code = "jmp +0\njmp -1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.apply_fix(), None);
+    }
+
+    #[test]
+    fn test_diff() {
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        let fixed = boot_code.apply_fix().unwrap();
+        assert_eq!(
+            boot_code.diff(&fixed),
+            Ok(vec![(7, Instruction::Jmp(-4), Instruction::Nop(-4))])
+        );
+        assert_eq!(boot_code.diff(&boot_code), Ok(vec![]));
+
+        let other = "nop +0\nacc +2".parse::<BootCode>().unwrap();
+        let err = boot_code.diff(&other).unwrap_err();
+        assert_eq!(err, LengthMismatch { left: 9, right: 2 });
+        assert_eq!(
+            err.to_string(),
+            "cannot diff a program of 9 instructions with one of 2"
+        );
+    }
+
+    #[test]
+    fn test_already_terminating() {
+        let boot_code = "nop +1\nacc +2\njmp +1".parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.run_with_fix(),
+            Some((None, BootCodeResult::Terminated(2)))
+        );
+        assert_eq!(BootCode::part2(&boot_code), "Terminated(2)");
+        assert_eq!(boot_code.all_fixes(), vec![(0, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_no_fix() {
+        let boot_code = "jmp +0\njmp -1".parse::<BootCode>().unwrap();
+        assert_eq!(boot_code.run_with_fix(), None);
+        assert_eq!(
+            boot_code.find_fix(),
+            Err(NoFix {
+                considered: 1,
+                viable: 0
+            })
+        );
+
+        let boot_code = "nop +0\nacc +1\njmp +0\njmp -2"
+            .parse::<BootCode>()
+            .unwrap();
+        assert_eq!(
+            boot_code.find_fix(),
+            Err(NoFix {
+                considered: 2,
+                viable: 0
+            })
+        );
+
+        let boot_code = "nop +0\nacc +1\njmp +2\nacc +1\njmp -3\njmp -3"
+            .parse::<BootCode>()
+            .unwrap();
+        let err = boot_code.find_fix().unwrap_err();
+        assert_eq!(
+            err,
+            NoFix {
+                considered: 3,
+                viable: 0
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "No single nop/jmp swap terminates this program (considered 3 executed \
+             nop/jmp instructions, 0 viable)"
+        );
+
+        // Swapping the jump reaches the end, but the accumulator overflows
+        // on the way.
+        let boot_code = "acc +2000000000\njmp +0\nacc +2000000000"
+            .parse::<BootCode>()
+            .unwrap();
+        let err = boot_code.find_fix().unwrap_err();
+        assert_eq!(
+            err,
+            NoFix {
+                considered: 1,
+                viable: 1
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "No single nop/jmp swap terminates this program (considered 1 executed \
+             nop/jmp instruction, 1 viable)"
+        );
+
+        let boot_code = EXAMPLE.parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.find_fix(),
+            Ok((Some(7), BootCodeResult::Terminated(8)))
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction() {
+        assert_eq!(
+            "acc +3".parse::<Instruction>().unwrap(),
+            Instruction::Acc(3)
+        );
+        assert_eq!(
+            "jmp -4".parse::<Instruction>().unwrap(),
+            Instruction::Jmp(-4)
+        );
+        assert_eq!(
+            " nop  +0 ".parse::<Instruction>().unwrap(),
+            Instruction::Nop(0)
+        );
+
+        let err = "acc".parse::<Instruction>().unwrap_err();
+        assert!(matches!(err, SyntaxError::WrongTokenCount(1)));
+        let err = "acc +1 +2".parse::<Instruction>().unwrap_err();
+        assert!(matches!(err, SyntaxError::WrongTokenCount(3)));
+        assert_eq!(
+            err.to_string(),
+            "expected an opcode and an operand, found 3 tokens"
+        );
+        let err = "".parse::<Instruction>().unwrap_err();
+        assert!(matches!(err, SyntaxError::WrongTokenCount(0)));
+
+        let err = "hlt +0".parse::<Instruction>().unwrap_err();
+        assert!(matches!(err, SyntaxError::UnknownOpcode(ref op) if op == "hlt"));
+
+        let err = "jmp four".parse::<Instruction>().unwrap_err();
+        assert!(matches!(err, SyntaxError::InvalidOperand(_)));
+        let err = "jmp 99999999999".parse::<Instruction>().unwrap_err();
+        assert!(matches!(err, SyntaxError::InvalidOperand(_)));
+    }
+
+    #[test]
+    fn test_parse_operands() {
+        let operand = |s: &str| match format!("acc {}", s).parse::<Instruction>() {
+            Ok(Instruction::Acc(val)) => Some(val),
+            _ => None,
+        };
+
+        assert_eq!(operand("+007"), Some(7));
+        assert_eq!(operand("-007"), Some(-7));
+        assert_eq!(operand("007"), Some(7));
+        assert_eq!(operand("+0"), Some(0));
+        assert_eq!(operand("-0"), Some(0));
+        assert_eq!(operand("+2147483647"), Some(i32::MAX));
+        assert_eq!(operand("-2147483648"), Some(i32::MIN));
+
+        for s in &["+", "-", "+-1", "--1", "1+", "0x10", "1_000", "+2147483648"] {
+            assert_eq!(operand(s), None, "operand {:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        let program = "\
+; sets the accumulator to 3 and loops
+nop +0
+
+acc +3 ; trailing comment
+   ; indented comment
+jmp -2;no space
+";
+        let boot_code = program.parse::<BootCode>().unwrap();
+        assert_eq!(boot_code, "nop +0\nacc +3\njmp -2".parse().unwrap());
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(3));
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        let boot_code = BootCode::try_from_bytes(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(boot_code, EXAMPLE.parse().unwrap());
+
+        let err = BootCode::try_from_bytes(b"nop +0\nacc \xff1").unwrap_err();
+        assert!(matches!(err, ParseError::Io(ref err) if err.kind() == io::ErrorKind::InvalidData));
+
+        for bytes in &[
+            &b""[..],
+            b"\n\n",
+            b"jmp",
+            b"jmp +",
+            b"acc --1",
+            b"\0\0",
+            b"nop +0 ;\xc3",
+        ] {
+            let _ = BootCode::try_from_bytes(bytes);
+        }
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let boot_code = INPUT.parse::<BootCode>().unwrap();
+        let bytes = boot_code.to_bytes();
+        assert_eq!(bytes.len(), boot_code.len() * 5);
+        assert_eq!(BootCode::from_bytes(&bytes).unwrap(), boot_code);
+
+        let boot_code = "nop +0\nacc -2\njmp +258".parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.to_bytes(),
+            vec![0, 0, 0, 0, 0, 1, 254, 255, 255, 255, 2, 2, 1, 0, 0]
+        );
+        assert_eq!(BootCode::from_bytes(&[]).unwrap(), BootCode::new(vec![]));
+
+        let err = BootCode::from_bytes(&[1, 1, 0, 0, 0, 2, 1]).unwrap_err();
+        assert_eq!(err, DecodeError::Truncated(5));
+        let err = BootCode::from_bytes(&[1, 1, 0, 0, 0, 3, 1, 0, 0, 0]).unwrap_err();
+        assert_eq!(err, DecodeError::UnknownOpcode(5, 3));
+        assert_eq!(err.to_string(), "byte 5: unknown opcode 0x03");
+    }
+
+    #[test]
+    fn test_parse_from_reader() {
+        let boot_code = BootCode::from_reader(&b"nop +0\nacc +3\njmp -2\n"[..]).unwrap();
+        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(3));
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn test_gzip_input() {
+        let boot_code = BootCode::from_file("example.txt.gz").unwrap();
+        assert_eq!(boot_code, EXAMPLE.parse::<BootCode>().unwrap());
+        let boot_code = BootCode::from_reader(File::open("example.txt.gz").unwrap()).unwrap();
+        assert_eq!(boot_code, EXAMPLE.parse::<BootCode>().unwrap());
+    }
+
+    fn format_program(boot_code: &BootCode) -> String {
+        boot_code
+            .instructions
+            .iter()
+            .map(|ins| ins.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        let formatted = format_program(&INPUT.parse().unwrap());
+        assert_eq!(formatted, INPUT.trim_end());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_round_trip() {
+        let boot_code = INPUT.parse::<BootCode>().unwrap();
+        let json = boot_code.to_json();
+        assert_eq!(BootCode::from_json(&json).unwrap(), boot_code);
+
+        let boot_code = "nop +0\nacc +1\njmp -4".parse::<BootCode>().unwrap();
+        assert_eq!(
+            boot_code.to_json(),
+            r#"[{"op":"nop","val":0},{"op":"acc","val":1},{"op":"jmp","val":-4}]"#
+        );
+        assert!(BootCode::from_json(r#"[{"op":"hlt","val":0}]"#).is_err());
+    }
+
+    #[test]
+    fn test_aoc_error() {
+        let err = AocError::from("nop +0\njmpp +4".parse::<BootCode>().unwrap_err());
+        assert!(matches!(err, AocError::UnknownOpcode { line: 2, ref opcode } if opcode == "jmpp"));
+        assert_eq!(err.to_string(), "line 2: unknown opcode 'jmpp'");
+
+        let err = AocError::from("acc".parse::<BootCode>().unwrap_err());
+        assert!(matches!(err, AocError::Syntax { line: 1, .. }));
+        let err = AocError::from(BootCode::from_file("does-not-exist.txt").unwrap_err());
+        assert!(matches!(err, AocError::Io(_)));
+    }
+
+    #[test]
+    fn test_parse_whitespace() {
+        let boot_code = "nop\t+0   \n \t \n  acc  +1\t\n\njmp +4 \r\n"
+            .parse::<BootCode>()
+            .unwrap();
+        assert_eq!(
+            boot_code,
+            BootCode::new(vec![
+                Instruction::Nop(0),
+                Instruction::Acc(1),
+                Instruction::Jmp(4)
+            ])
+        );
+
+        let err = "nop +0\n   \nacc +1 +2\n".parse::<BootCode>().unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::Syntax(3, SyntaxError::WrongTokenCount(3))
+        ));
+        let err = "nop +0\t\tjmp\n".parse::<BootCode>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1: expected an opcode and an operand, found 3 tokens"
+        );
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let err = BootCode::from_file("does-not-exist.txt").unwrap_err();
+        assert!(matches!(err, ParseError::Io(_)));
+
+        let err = "nop +0\nacc\njmpp +4".parse::<BootCode>().unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::Syntax(2, SyntaxError::WrongTokenCount(1))
+        ));
+
+        let err = "nop +0\nacc +1\njmpp +4".parse::<BootCode>().unwrap_err();
+        assert_eq!(err.to_string(), "line 3: unknown opcode 'jmpp'");
+
+        let err = "; comment\n\nacc +1 ; trailing\njmp\n"
+            .parse::<BootCode>()
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            ParseError::Syntax(4, SyntaxError::WrongTokenCount(1))
+        ));
+
+        let err = "acc x1".parse::<BootCode>().unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::Syntax(1, SyntaxError::InvalidOperand(_))
+        ));
+    }
+}
@@ -1,141 +1,113 @@
 use std::env;
-use std::fs;
-use std::path::Path;
+use std::io;
 use std::process;
 
-#[derive(Clone)]
-enum Instruction {
-    Nop(i32),
-    Acc(i32),
-    Jmp(i32),
+use aoc::{AocError, Solver};
+use day08::{BootCode, BootCodeResult};
+
+// Formats a result as a JSON object such as `{"result":"Cyclic","acc":5}`.
+fn result_to_json(result: &BootCodeResult) -> String {
+    let (name, key, val) = match result {
+        BootCodeResult::Terminated(acc) => ("Terminated", "acc", i64::from(*acc)),
+        BootCodeResult::Cyclic(acc) => ("Cyclic", "acc", i64::from(*acc)),
+        BootCodeResult::Exhausted(acc) => ("Exhausted", "acc", i64::from(*acc)),
+        BootCodeResult::OutOfBounds(ip) => ("OutOfBounds", "ip", *ip as i64),
+        BootCodeResult::Overflow(ip) => ("Overflow", "ip", *ip as i64),
+    };
+    format!(r#"{{"result":"{}","{}":{}}}"#, name, key, val)
 }
 
-#[derive(Debug, PartialEq)]
-enum BootCodeResult {
-    Terminated(i32),
-    Cyclic(i32),
-}
-
-fn jmp_ip(ip: usize, jmp: i32) -> usize {
-    (ip as isize + jmp as isize) as usize
-}
-
-struct BootCode {
-    instructions: Vec<Instruction>,
+// Same as `BootCode::part2`, but also telling which instruction was
+// swapped.
+fn part2_with_patch(boot_code: &BootCode) -> String {
+    match boot_code.find_fix() {
+        Ok((Some(ip), result)) => format!("{:?} (patched ip {})", result, ip),
+        Ok((None, result)) => format!("{:?} (no patch needed)", result),
+        Err(err) => err.to_string(),
+    }
 }
 
-impl BootCode {
-    fn new(instructions: Vec<Instruction>) -> Self {
-        BootCode { instructions }
+// Reports how long `f` took only when timing was asked for.
+fn timed<T>(timing: bool, label: &str, f: impl FnOnce() -> T) -> T {
+    if timing {
+        aoc::time_it(label, f)
+    } else {
+        f()
     }
+}
 
-    fn run(&self) -> BootCodeResult {
-        let mut acc = 0;
-        let mut ip = 0;
-        let mut executed = vec![false; self.instructions.len()];
-
-        while ip != self.instructions.len() {
-            if executed[ip] {
-                return BootCodeResult::Cyclic(acc);
+fn solve(
+    file_name: &str,
+    json: bool,
+    timing: bool,
+    trace_csv: bool,
+    show_patch: bool,
+) -> Result<(), AocError> {
+    let boot_code = timed(timing, "parse", || {
+        if file_name == "-" {
+            BootCode::from_reader(io::stdin())
+        } else {
+            BootCode::from_file(file_name)
+        }
+    })?;
+
+    if trace_csv {
+        print!("{}", boot_code.trace_csv());
+    } else if json {
+        let part1 = timed(timing, "part1", || boot_code.run());
+        let part2 = match timed(timing, "part2", || boot_code.run_with_fix()) {
+            Some((_, result)) => result_to_json(&result),
+            None => "null".to_owned(),
+        };
+        println!(
+            r#"{{"part1": {}, "part2": {}}}"#,
+            result_to_json(&part1),
+            part2
+        );
+    } else {
+        let part1 = timed(timing, "part1", || BootCode::part1(&boot_code));
+        let part2 = timed(timing, "part2", || {
+            if show_patch {
+                part2_with_patch(&boot_code)
             } else {
-                executed[ip] = true;
-            }
-
-            match self.instructions[ip] {
-                Instruction::Nop(_) => {
-                    ip += 1;
-                }
-                Instruction::Acc(val) => {
-                    acc += val;
-                    ip += 1;
-                }
-                Instruction::Jmp(val) => {
-                    ip = jmp_ip(ip, val);
-                }
+                BootCode::part2(&boot_code)
             }
-        }
-
-        BootCodeResult::Terminated(acc)
+        });
+        println!("Result (Part 1): {}", part1);
+        println!("Result (Part 2): {}", part2);
     }
+    Ok(())
+}
 
-    fn run_with_fix(&self) -> BootCodeResult {
-        for (ip, ins) in self.instructions.iter().enumerate() {
-            match ins {
-                Instruction::Nop(val) => {
-                    let mut new_instructions = self.instructions.clone();
-                    new_instructions[ip] = Instruction::Jmp(*val);
-                    let r = BootCode::new(new_instructions).run();
-                    if let BootCodeResult::Terminated(_) = r {
-                        return r;
-                    }
-                }
-                Instruction::Jmp(val) => {
-                    let mut new_instructions = self.instructions.clone();
-                    new_instructions[ip] = Instruction::Nop(*val);
-                    let r = BootCode::new(new_instructions).run();
-                    if let BootCodeResult::Terminated(_) = r {
-                        return r;
-                    }
-                }
-                Instruction::Acc(_) => (),
-            }
+// Removes `flag` from the arguments, returning whether it was there.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    match args.iter().position(|arg| arg == flag) {
+        Some(i) => {
+            args.remove(i);
+            true
         }
-
-        self.run()
+        None => false,
     }
 }
 
-fn parse_input(file_name: impl AsRef<Path>) -> Vec<Instruction> {
-    fs::read_to_string(&file_name)
-        .unwrap()
-        .lines()
-        .map(|x| {
-            let mut parts = x.split_whitespace();
-            let (op, val) = (
-                parts.next().unwrap(),
-                parts.next().unwrap().parse::<i32>().unwrap(),
-            );
-            match op {
-                "nop" => Instruction::Nop(val),
-                "acc" => Instruction::Acc(val),
-                "jmp" => Instruction::Jmp(val),
-                _ => panic!("Invalid operation: {}", op),
-            }
-        })
-        .collect()
-}
-
 fn main() {
-    if env::args().count() != 2 {
-        eprintln!("USAGE: {} FILE", env::args().next().unwrap());
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let json = take_flag(&mut args, "--json");
+    let timing = take_flag(&mut args, "--timing");
+    let trace_csv = take_flag(&mut args, "--trace-csv");
+    let show_patch = take_flag(&mut args, "--show-patch");
+
+    if args.len() > 1 {
+        eprintln!(
+            "USAGE: {} [--json] [--timing] [--trace-csv] [--show-patch] [FILE]",
+            env::args().next().unwrap()
+        );
         process::exit(1);
     }
 
-    let instructions = parse_input(env::args().nth(1).unwrap());
-    let boot_code = BootCode::new(instructions);
-    let part1 = boot_code.run();
-    let part2 = boot_code.run_with_fix();
-    println!("Result (Part 1): {:?}", part1);
-    println!("Result (Part 2): {:?}", part2);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_example_input() {
-        let instructions = parse_input("example.txt");
-        let boot_code = BootCode::new(instructions);
-        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(5));
-        assert_eq!(boot_code.run_with_fix(), BootCodeResult::Terminated(8));
-    }
-
-    #[test]
-    fn test_puzzle_input() {
-        let instructions = parse_input("input.txt");
-        let boot_code = BootCode::new(instructions);
-        assert_eq!(boot_code.run(), BootCodeResult::Cyclic(1810));
-        assert_eq!(boot_code.run_with_fix(), BootCodeResult::Terminated(969));
+    let file_name = args.pop().unwrap_or_else(|| "-".to_owned());
+    if let Err(err) = solve(&file_name, json, timing, trace_csv, show_patch) {
+        eprintln!("{}: {}", file_name, err);
+        process::exit(1);
     }
 }
@@ -0,0 +1,121 @@
+use aoc::testing::{run, run_with_input, run_with_stdin};
+
+#[test]
+fn test_stdin() {
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_08"),
+        &[],
+        include_bytes!("../example.txt"),
+    );
+    assert_eq!(
+        output,
+        "Result (Part 1): Cyclic(5)\nResult (Part 2): Terminated(8)\n"
+    );
+}
+
+#[test]
+fn test_timing() {
+    let output = run(env!("CARGO_BIN_EXE_08"), &["--timing", "example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1): Cyclic(5)\nResult (Part 2): Terminated(8)\n"
+    );
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let labels: Vec<_> = stderr
+        .lines()
+        .map(|line| line.split(':').next().unwrap())
+        .collect();
+    assert_eq!(labels, vec!["parse", "part1", "part2"]);
+}
+
+#[test]
+fn test_file() {
+    let output = run(env!("CARGO_BIN_EXE_08"), &["example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1): Cyclic(5)\nResult (Part 2): Terminated(8)\n"
+    );
+}
+
+#[test]
+fn test_show_patch() {
+    let output = run(env!("CARGO_BIN_EXE_08"), &["--show-patch", "example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1): Cyclic(5)\nResult (Part 2): Terminated(8) (patched ip 7)\n"
+    );
+
+    let output = run_with_stdin(env!("CARGO_BIN_EXE_08"), &["--show-patch"], b"acc +1\n");
+    assert_eq!(
+        output,
+        "Result (Part 1): Terminated(1)\nResult (Part 2): Terminated(1) (no patch needed)\n"
+    );
+}
+
+#[test]
+fn test_json() {
+    let output = run(env!("CARGO_BIN_EXE_08"), &["--json", "example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        concat!(
+            r#"{"part1": {"result":"Cyclic","acc":5}, "#,
+            r#""part2": {"result":"Terminated","acc":8}}"#,
+            "\n"
+        )
+    );
+
+    let output = run_with_stdin(env!("CARGO_BIN_EXE_08"), &["--json"], b"jmp +0\njmp -1\n");
+    assert_eq!(
+        output,
+        concat!(
+            r#"{"part1": {"result":"Cyclic","acc":0}, "part2": null}"#,
+            "\n"
+        )
+    );
+}
+
+#[test]
+fn test_trace_csv() {
+    let output = run(env!("CARGO_BIN_EXE_08"), &["--trace-csv", "example.txt"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.lines().take(5).collect::<Vec<_>>(),
+        vec![
+            "step,ip,acc,opcode",
+            "0,0,0,nop",
+            "1,1,0,acc",
+            "2,2,1,jmp",
+            "3,6,1,acc"
+        ]
+    );
+    assert_eq!(stdout.lines().count(), 8);
+}
+
+#[test]
+fn test_usage() {
+    let output = run(env!("CARGO_BIN_EXE_08"), &["example.txt", "example.txt"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("USAGE: "));
+}
+
+#[test]
+fn test_stdin_without_file() {
+    let output = run_with_input(
+        env!("CARGO_BIN_EXE_08"),
+        &[],
+        include_bytes!("../example.txt"),
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1): Cyclic(5)\nResult (Part 2): Terminated(8)\n"
+    );
+}
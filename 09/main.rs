@@ -1,6 +1,5 @@
 use std::collections::HashSet;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -38,7 +37,7 @@ fn find_encryption_weakness(numbers: &[usize], error: usize) -> Option<usize> {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> Vec<usize> {
-    fs::read_to_string(&file_name)
+    aoc::read_input(&file_name)
         .unwrap()
         .lines()
         .map(|x| x.parse::<usize>().unwrap())
@@ -1,10 +1,9 @@
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
 fn parse_input(file_name: impl AsRef<Path>) -> Vec<usize> {
-    let mut jolts: Vec<_> = fs::read_to_string(&file_name)
+    let mut jolts: Vec<_> = aoc::read_input(&file_name)
         .unwrap()
         .lines()
         .map(|x| x.parse().unwrap())
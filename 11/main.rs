@@ -1,5 +1,4 @@
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -107,7 +106,7 @@ impl SeatingSystem {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> Vec<Vec<SeatStatus>> {
-    fs::read_to_string(&file_name)
+    aoc::read_input(&file_name)
         .unwrap()
         .lines()
         .map(|x| {
@@ -1,5 +1,4 @@
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -66,7 +65,7 @@ fn solve_part2(moves: &Vec<Move>) -> isize {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> Vec<Move> {
-    fs::read_to_string(&file_name)
+    aoc::read_input(&file_name)
         .unwrap()
         .lines()
         .map(|x| {
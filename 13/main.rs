@@ -1,5 +1,4 @@
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -33,7 +32,7 @@ fn solve_part2(schedule: &[(usize, usize)]) -> usize {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> (usize, Vec<(usize, usize)>) {
-    let content = fs::read_to_string(&file_name).unwrap();
+    let content = aoc::read_input(&file_name).unwrap();
     let mut lines = content.lines();
 
     let timestamp = lines.next().unwrap().parse().unwrap();
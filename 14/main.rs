@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -101,7 +100,7 @@ fn parse_instruction(line: &str) -> Instruction {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> Vec<Instruction> {
-    let content = fs::read_to_string(&file_name).unwrap();
+    let content = aoc::read_input(&file_name).unwrap();
     content.lines().map(|x| parse_instruction(x)).collect()
 }
 
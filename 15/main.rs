@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -25,7 +24,7 @@ fn solve(numbers: &[usize], target_turn: usize) -> usize {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> Vec<usize> {
-    fs::read_to_string(&file_name)
+    aoc::read_input(&file_name)
         .unwrap()
         .trim()
         .split(',')
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -80,7 +79,7 @@ fn solve_part2(ranges: &RangeMap, ticket: &[usize], nearby_tickets: &[Vec<usize>
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> (RangeMap, Vec<usize>, Vec<Vec<usize>>) {
-    let content = fs::read_to_string(file_name).unwrap();
+    let content = aoc::read_input(file_name).unwrap();
     let mut blocks = content.split("\n\n");
 
     let re = Regex::new(r"(?P<f>.+): (?P<s1>\d+)-(?P<e1>\d+) or (?P<s2>\d+)-(?P<e2>\d+)").unwrap();
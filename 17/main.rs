@@ -1,5 +1,4 @@
 use std::env;
-use std::fs;
 use std::process;
 
 use itertools::iproduct;
@@ -136,7 +135,7 @@ fn main() {
         process::exit(1);
     }
 
-    let input = fs::read_to_string(&env::args().nth(1).unwrap()).unwrap();
+    let input = aoc::read_input(&env::args().nth(1).unwrap()).unwrap();
     let num_cycles = env::args().nth(2).unwrap().parse::<usize>().unwrap();
 
     let mut cube_system = ConwayCubeSystem::new(&input, num_cycles, false);
@@ -152,6 +151,7 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_example_input_part1() {
@@ -1,5 +1,4 @@
 use std::env;
-use std::fs;
 use std::process;
 
 #[derive(PartialEq)]
@@ -185,7 +184,7 @@ fn main() {
         process::exit(1);
     }
 
-    let input = fs::read_to_string(&env::args().nth(1).unwrap()).unwrap();
+    let input = aoc::read_input(&env::args().nth(1).unwrap()).unwrap();
     let part1 = calculate_sums_part1(&input);
     let part2a = calculate_sums_part2_inplace(&input);
     let part2b = calculate_sums_part2_shunting_yard(&input);
@@ -197,6 +196,7 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_example_input_1() {
@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -61,7 +60,7 @@ fn parse_rule(line: &str) -> (usize, Rule) {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> (HashMap<usize, Rule>, Vec<String>) {
-    let content = fs::read_to_string(file_name).unwrap();
+    let content = aoc::read_input(file_name).unwrap();
     let mut blocks = content.split("\n\n");
 
     let rules = blocks
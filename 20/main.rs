@@ -1,6 +1,5 @@
 use std::collections::VecDeque;
 use std::env;
-use std::fs;
 use std::mem;
 use std::path::Path;
 use std::process;
@@ -288,7 +287,7 @@ fn parse_tile(tile: &str) -> Tile {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> VecDeque<Tile> {
-    fs::read_to_string(file_name)
+    aoc::read_input(file_name)
         .unwrap()
         .trim()
         .split("\n\n")
@@ -0,0 +1,33 @@
+use aoc::testing::{run, run_with_stdin};
+
+#[test]
+fn test_stdin() {
+    let output = run_with_stdin(
+        env!("CARGO_BIN_EXE_20"),
+        &[],
+        include_bytes!("../example.txt"),
+    );
+    assert_eq!(
+        output,
+        "Result (Part 1): 20899048083289\nResult (Part 2): 273\n"
+    );
+}
+
+#[test]
+fn test_file() {
+    let output = run(env!("CARGO_BIN_EXE_20"), &["example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1): 20899048083289\nResult (Part 2): 273\n"
+    );
+}
+
+#[test]
+fn test_usage() {
+    let output = run(env!("CARGO_BIN_EXE_20"), &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("USAGE: "));
+}
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -65,7 +64,7 @@ fn find_allergens(ingredients: &[String], allergens: &AlergenIngredientsLists) -
 
 fn parse_input(file_name: impl AsRef<Path>) -> (Vec<String>, AlergenIngredientsLists) {
     let re = Regex::new(r"(?P<ingredients>.+) \(contains (?P<allergens>.+)\)").unwrap();
-    let content = fs::read_to_string(file_name).unwrap();
+    let content = aoc::read_input(file_name).unwrap();
 
     let mut ingredients = vec![];
     let mut allergens = HashMap::new();
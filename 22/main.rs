@@ -1,7 +1,6 @@
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -82,7 +81,7 @@ fn recursive_combat_score(deck1: &mut Deck, deck2: &mut Deck) -> usize {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> (Deck, Deck) {
-    let content = fs::read_to_string(file_name).unwrap();
+    let content = aoc::read_input(file_name).unwrap();
     let mut blocks = content.split("\n\n");
 
     let deck1 = blocks
@@ -0,0 +1,20 @@
+use aoc::testing::run;
+
+#[test]
+fn test_example() {
+    let output = run(env!("CARGO_BIN_EXE_23"), &["389125467"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 1): 67384529\nResult (Part 2): 149245887792\n"
+    );
+}
+
+#[test]
+fn test_usage() {
+    let output = run(env!("CARGO_BIN_EXE_23"), &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("USAGE: "));
+}
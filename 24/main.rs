@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
-use std::fs;
 use std::path::Path;
 use std::process;
 
@@ -37,7 +36,7 @@ fn iterate(mut tiles: Tiles) -> Tiles {
 }
 
 fn parse_input(file_name: impl AsRef<Path>) -> Tiles {
-    let content = fs::read_to_string(file_name).unwrap();
+    let content = aoc::read_input(file_name).unwrap();
     let mut tiles = HashSet::new();
 
     /*
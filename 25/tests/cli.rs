@@ -0,0 +1,20 @@
+use aoc::testing::run;
+
+#[test]
+fn test_example() {
+    let output = run(env!("CARGO_BIN_EXE_25"), &["5764801", "17807724"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result: 14897079\n"
+    );
+}
+
+#[test]
+fn test_usage() {
+    let output = run(env!("CARGO_BIN_EXE_25"), &["5764801"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("USAGE: "));
+}
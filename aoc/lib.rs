@@ -0,0 +1,244 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::num::ParseIntError;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Instant;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[derive(Debug)]
+pub enum ParseLinesError<E> {
+    Io(io::Error),
+    // The 1-based line number, the text of the line and the parse error.
+    Parse(usize, String, E),
+}
+
+impl<E: fmt::Display> fmt::Display for ParseLinesError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseLinesError::Io(err) => write!(f, "{}", err),
+            ParseLinesError::Parse(line, text, err) => {
+                write!(f, "line {}: cannot parse '{}': {}", line, text, err)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for ParseLinesError<E> {}
+
+impl<E> From<io::Error> for ParseLinesError<E> {
+    fn from(err: io::Error) -> Self {
+        ParseLinesError::Io(err)
+    }
+}
+
+// Error shared by the programs, which each day's parse errors convert into.
+#[derive(Debug, thiserror::Error)]
+pub enum AocError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("invalid number: {0}")]
+    ParseInt(#[from] ParseIntError),
+    #[error("line {line}: unknown opcode '{opcode}'")]
+    UnknownOpcode { line: usize, opcode: String },
+    #[error("line {line}: row has {width} columns, expected {expected}")]
+    RaggedMap {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+    #[error("the input is empty")]
+    EmptyInput,
+    #[error("line {line}: {message}")]
+    Syntax { line: usize, message: String },
+}
+
+impl<E: fmt::Display> From<ParseLinesError<E>> for AocError {
+    fn from(err: ParseLinesError<E>) -> Self {
+        match err {
+            ParseLinesError::Io(err) => AocError::Io(err),
+            ParseLinesError::Parse(line, text, err) => AocError::Syntax {
+                line,
+                message: format!("cannot parse '{}': {}", text, err),
+            },
+        }
+    }
+}
+
+// Common interface to the puzzle of each day: the input is parsed once from
+// the puzzle text and then both parts are solved from it.
+pub trait Solver {
+    type Input;
+    type Err: fmt::Display;
+
+    fn parse(s: &str) -> Result<Self::Input, Self::Err>;
+    fn part1(input: &Self::Input) -> String;
+    fn part2(input: &Self::Input) -> String;
+}
+
+// Reads the puzzle input from the given file, or from the standard input
+// when the file name is "-", parses it once and prints the result of both
+// parts.
+pub fn run_both_parts<S: Solver>(file_name: impl AsRef<Path>) -> Result<(), AocError>
+where
+    S::Err: Into<AocError>,
+{
+    let input = S::parse(&read_input(file_name)?).map_err(Into::into)?;
+    println!("Result (Part 1): {}", S::part1(&input));
+    println!("Result (Part 2): {}", S::part2(&input));
+    Ok(())
+}
+
+// Reads all the text from `reader`. With the flate2 feature, gzip
+// compressed content is told apart by its magic number and decompressed.
+pub fn read_all(mut reader: impl Read) -> io::Result<String> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+
+    #[cfg(feature = "flate2")]
+    {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            let mut content = String::new();
+            flate2::read::MultiGzDecoder::new(&bytes[..]).read_to_string(&mut content)?;
+            return Ok(content);
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+// Like `fs::read_to_string`, but also reads gzip compressed files, such as
+// `input.txt.gz`, with the flate2 feature.
+pub fn read_to_string(file_name: impl AsRef<Path>) -> io::Result<String> {
+    read_all(File::open(file_name)?)
+}
+
+// Reads the puzzle input from the given file, or from the standard input
+// when the file name is "-".
+pub fn read_input(file_name: impl AsRef<Path>) -> io::Result<String> {
+    if file_name.as_ref() == Path::new("-") {
+        read_all(io::stdin())
+    } else {
+        read_to_string(file_name)
+    }
+}
+
+// Lines may end in either "\n" or "\r\n". As with `read_input`, "-" reads
+// the standard input.
+pub fn read_lines(file_name: impl AsRef<Path>) -> io::Result<Vec<String>> {
+    Ok(read_input(file_name)?
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line).to_owned())
+        .collect())
+}
+
+pub fn parse_lines<T: FromStr>(
+    file_name: impl AsRef<Path>,
+) -> Result<Vec<T>, ParseLinesError<T::Err>> {
+    read_lines(file_name)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| match line.parse() {
+            Ok(val) => Ok(val),
+            Err(err) => Err(ParseLinesError::Parse(i + 1, line, err)),
+        })
+        .collect()
+}
+
+// Runs `f`, printing to the standard error how long it took under the
+// given label, such as "parse: 42µs".
+pub fn time_it<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let val = f();
+    eprintln!("{}: {}µs", label, start.elapsed().as_micros());
+    val
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_lines() {
+        assert_eq!(read_lines("numbers.txt").unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(read_lines("numbers-crlf.txt").unwrap(), vec!["1", "2", "3"]);
+        assert!(read_lines("does-not-exist.txt").is_err());
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn test_read_gzip() {
+        assert_eq!(read_lines("numbers.txt.gz").unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(
+            parse_lines::<i32>("numbers.txt.gz").unwrap(),
+            parse_lines::<i32>("numbers.txt").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_aoc_error() {
+        let err = AocError::from(parse_lines::<i32>("invalid-numbers.txt").unwrap_err());
+        assert_eq!(
+            err.to_string(),
+            "line 2: cannot parse 'two': invalid digit found in string"
+        );
+
+        let err = AocError::from(parse_lines::<i32>("does-not-exist.txt").unwrap_err());
+        assert!(matches!(err, AocError::Io(_)));
+
+        let err = AocError::from("x".parse::<i32>().unwrap_err());
+        assert_eq!(
+            err.to_string(),
+            "invalid number: invalid digit found in string"
+        );
+    }
+
+    struct Sum;
+
+    impl Solver for Sum {
+        type Input = Vec<i32>;
+        type Err = ParseIntError;
+
+        fn parse(s: &str) -> Result<Self::Input, Self::Err> {
+            s.lines().map(str::parse).collect()
+        }
+
+        fn part1(input: &Self::Input) -> String {
+            input.iter().sum::<i32>().to_string()
+        }
+
+        fn part2(input: &Self::Input) -> String {
+            input.iter().product::<i32>().to_string()
+        }
+    }
+
+    #[test]
+    fn test_run_both_parts() {
+        assert!(run_both_parts::<Sum>("numbers.txt").is_ok());
+        let err = run_both_parts::<Sum>("invalid-numbers.txt").unwrap_err();
+        assert!(matches!(err, AocError::ParseInt(_)));
+        let err = run_both_parts::<Sum>("does-not-exist.txt").unwrap_err();
+        assert!(matches!(err, AocError::Io(_)));
+    }
+
+    #[test]
+    fn test_time_it() {
+        assert_eq!(time_it("sum", || (1..=10).sum::<i32>()), 55);
+        assert!(time_it("parse", || parse_lines::<i32>("numbers.txt")).is_ok());
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(parse_lines::<i32>("numbers.txt").unwrap(), vec![1, 2, 3]);
+        let err = parse_lines::<i32>("invalid-numbers.txt").unwrap_err();
+        assert!(matches!(err, ParseLinesError::Parse(2, ref text, _) if text == "two"));
+        assert_eq!(
+            err.to_string(),
+            "line 2: cannot parse 'two': invalid digit found in string"
+        );
+    }
+}
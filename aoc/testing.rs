@@ -0,0 +1,32 @@
+// Helpers for the integration tests of each day's program.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+// Runs the program with `-` as the input file, piping `input` to it.
+pub fn run_with_stdin(program: &str, args: &[&str], input: &[u8]) -> String {
+    let mut all_args = vec!["-"];
+    all_args.extend_from_slice(args);
+    let output = run_with_input(program, &all_args, input);
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// Runs the program with the given arguments, piping `input` to it, whether
+// it succeeds or not.
+pub fn run_with_input(program: &str, args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+// Runs the program with the given arguments.
+pub fn run(program: &str, args: &[&str]) -> Output {
+    Command::new(program).args(args).output().unwrap()
+}
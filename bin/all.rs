@@ -0,0 +1,100 @@
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant};
+
+use aoc::{AocError, Solver};
+use day01::ExpenseReport;
+use day03::Map;
+use day08::BootCode;
+
+type Run = fn(&str) -> Result<(String, String), AocError>;
+
+const DAYS: &[(&str, Run)] = &[
+    ("01", run::<ExpenseReport>),
+    ("03", run::<Map>),
+    ("08", run::<BootCode>),
+];
+
+fn run<S: Solver>(content: &str) -> Result<(String, String), AocError>
+where
+    S::Err: Into<AocError>,
+{
+    let input = S::parse(content).map_err(Into::into)?;
+    Ok((S::part1(&input), S::part2(&input)))
+}
+
+fn main() {
+    if env::args().count() > 2 {
+        eprintln!("USAGE: {} [DIR]", env::args().next().unwrap());
+        process::exit(1);
+    }
+
+    // The directory holding a directory per day, by default the workspace.
+    let root = match env::args().nth(1) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."),
+    };
+    let mut rows = vec![];
+    let mut total = Duration::default();
+
+    for (day, run) in DAYS {
+        let file_name = root.join(day).join("input.txt");
+        let content = match aoc::read_to_string(&file_name) {
+            Ok(content) => content,
+            Err(_) => {
+                rows.push((day.to_string(), "skipped".to_owned(), String::new(), None));
+                continue;
+            }
+        };
+
+        let start = Instant::now();
+        let result = run(&content);
+        let elapsed = start.elapsed();
+        total += elapsed;
+
+        rows.push(match result {
+            Ok((part1, part2)) => (day.to_string(), part1, part2, Some(elapsed)),
+            Err(err) => (
+                day.to_string(),
+                format!("error: {}", err),
+                String::new(),
+                None,
+            ),
+        });
+    }
+
+    let width1 = rows.iter().map(|row| row.1.len()).max().unwrap_or(0).max(6);
+    let width2 = rows.iter().map(|row| row.2.len()).max().unwrap_or(0).max(6);
+    let format_time = |time: Duration| format!("{:.3}ms", time.as_secs_f64() * 1000.0);
+
+    println!(
+        "{:<5}{:<w1$}  {:<w2$}  Time",
+        "Day",
+        "Part 1",
+        "Part 2",
+        w1 = width1,
+        w2 = width2
+    );
+    for (day, part1, part2, time) in &rows {
+        let time = time.map(format_time).unwrap_or_default();
+        println!(
+            "{:<5}{:<w1$}  {:<w2$}  {}",
+            day,
+            part1,
+            part2,
+            time,
+            w1 = width1,
+            w2 = width2
+        );
+    }
+    println!(
+        "{:<5}{:<w1$}  {:<w2$}  {}",
+        "Total",
+        "",
+        "",
+        format_time(total),
+        w1 = width1,
+        w2 = width2
+    );
+}
@@ -0,0 +1,67 @@
+use std::env;
+use std::process;
+
+use aoc::{AocError, Solver};
+use day01::ExpenseReport;
+use day03::Map;
+use day08::BootCode;
+
+const DAYS: &[&str] = &["01", "03", "08"];
+
+fn solve<S: Solver>(part: &str, file_name: &str) -> Result<String, AocError>
+where
+    S::Err: Into<AocError>,
+{
+    let content = aoc::read_input(file_name)?;
+    let input = S::parse(&content).map_err(Into::into)?;
+    match part {
+        "1" => Ok(S::part1(&input)),
+        _ => Ok(S::part2(&input)),
+    }
+}
+
+// Lists the instructions of a day 8 program, with the targets of jumps.
+fn dump(_: &str, file_name: &str) -> Result<String, AocError> {
+    let content = aoc::read_input(file_name)?;
+    Ok(BootCode::parse(&content)?.disassemble())
+}
+
+fn main() {
+    if env::args().count() != 4 {
+        eprintln!(
+            "USAGE: {0} DAY PART FILE\n       {0} 08 dump FILE",
+            env::args().next().unwrap()
+        );
+        process::exit(1);
+    }
+
+    let day = env::args().nth(1).unwrap();
+    let part = env::args().nth(2).unwrap();
+    let file_name = env::args().nth(3).unwrap();
+
+    let solver = match (day.as_str(), part.as_str()) {
+        ("01", "1") | ("01", "2") => solve::<ExpenseReport>,
+        ("03", "1") | ("03", "2") => solve::<Map>,
+        ("08", "1") | ("08", "2") => solve::<BootCode>,
+        ("08", "dump") => dump,
+        _ => {
+            eprintln!("Unknown day and part: {} {}", day, part);
+            eprintln!("Available:");
+            for day in DAYS {
+                eprintln!("  {} 1", day);
+                eprintln!("  {} 2", day);
+            }
+            eprintln!("  08 dump");
+            process::exit(1);
+        }
+    };
+
+    match solver(&part, &file_name) {
+        Ok(listing) if part == "dump" => print!("{}", listing),
+        Ok(result) => println!("Result (Part {}): {}", part, result),
+        Err(err) => {
+            eprintln!("{}: {}", file_name, err);
+            process::exit(1);
+        }
+    }
+}
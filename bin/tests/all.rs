@@ -0,0 +1,27 @@
+use std::fs;
+
+use aoc::testing::run;
+
+#[test]
+fn test_all() {
+    // Only day 1 has an input, the example, so the other days are skipped.
+    let root = format!("{}/all", env!("CARGO_TARGET_TMPDIR"));
+    fs::create_dir_all(format!("{}/01", root)).unwrap();
+    fs::copy("../01/example.txt", format!("{}/01/input.txt", root)).unwrap();
+
+    let output = run(env!("CARGO_BIN_EXE_all"), &[&root]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<Vec<_>> = stdout
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0], vec!["Day", "Part", "1", "Part", "2", "Time"]);
+    assert_eq!(rows[1][..3], ["01", "Some(514579)", "Some(241861950)"]);
+    assert!(rows[1][3].ends_with("ms"));
+    assert_eq!(rows[2], vec!["03", "skipped"]);
+    assert_eq!(rows[3], vec!["08", "skipped"]);
+    assert_eq!(rows[4][0], "Total");
+}
@@ -0,0 +1,50 @@
+use aoc::testing::{run, run_with_input};
+
+#[test]
+fn test_part() {
+    let output = run(env!("CARGO_BIN_EXE_aoc"), &["08", "2", "../08/example.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Result (Part 2): Terminated(8)\n"
+    );
+}
+
+#[test]
+fn test_unknown_part() {
+    let available = "Available:\n  01 1\n  01 2\n  03 1\n  03 2\n  08 1\n  08 2\n  08 dump\n";
+    for (day, part) in &[("26", "1"), ("8", "3"), ("08", "3")] {
+        let output = run(env!("CARGO_BIN_EXE_aoc"), &[day, part, "../08/example.txt"]);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(output.stdout.is_empty());
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            format!("Unknown day and part: {} {}\n{}", day, part, available)
+        );
+    }
+}
+
+#[test]
+fn test_dump() {
+    let output = run(
+        env!("CARGO_BIN_EXE_aoc"),
+        &["08", "dump", "../08/example.txt"],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        include_str!("../../08/example-disassembly.txt")
+    );
+
+    let output = run_with_input(
+        env!("CARGO_BIN_EXE_aoc"),
+        &["08", "dump", "-"],
+        b"nop +0\nmul +2\n",
+    );
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "-: line 2: unknown opcode 'mul'\n"
+    );
+}
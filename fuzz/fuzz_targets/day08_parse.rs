@@ -0,0 +1,13 @@
+#![no_main]
+
+use day08::BootCode;
+use libfuzzer_sys::fuzz_target;
+
+// Any input must either be rejected with an error or give a program that
+// runs to some result, without panicking.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(boot_code) = BootCode::try_from_bytes(data) {
+        let _ = boot_code.run();
+        let _ = boot_code.run_with_fix();
+    }
+});